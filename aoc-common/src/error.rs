@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt;
+
+pub type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Where exactly the input stopped making sense
+/// Line parsers only know the line itself, the caller adds the line number and the file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub file: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// `token` should be a slice of `line`, that's how the column is found
+    pub fn new(line: &str, token: &str, message: &str) -> Self {
+        let start = line.as_ptr() as usize;
+        let token_start = token.as_ptr() as usize;
+
+        let offset = if token_start >= start && token_start + token.len() <= start + line.len() {
+            Some(token_start - start)
+        } else {
+            line.find(token)
+        };
+
+        // Columns are counted in chars starting from 1, like editors do
+        let column = match offset {
+            Some(offset) => line[..offset].chars().count() + 1,
+            None => line.chars().count() + 1,
+        };
+
+        ParseError {
+            file: None,
+            line: 0,
+            column,
+            token: token.to_owned(),
+            message: message.to_owned(),
+            source_line: line.to_owned(),
+        }
+    }
+
+    /// For errors about something missing at the end of the line
+    pub fn at_end(line: &str, message: &str) -> Self {
+        ParseError::new(line, &line[line.len()..], message)
+    }
+
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    pub fn in_file(mut self, file: &str) -> Self {
+        self.file = Some(file.to_owned());
+        self
+    }
+
+    /// Compiler style diagnostic with a caret under the offending token
+    pub fn render(&self) -> String {
+        let line_number = self.line.to_string();
+        let padding = " ".repeat(line_number.len());
+        let underline = "^".repeat(self.token.chars().count().max(1));
+
+        format!(
+            "error: {}\n{}--> {}\n{} |\n{} | {}\n{} | {}{}",
+            self.message,
+            padding,
+            self.location(),
+            padding,
+            line_number,
+            self.source_line,
+            padding,
+            " ".repeat(self.column - 1),
+            underline
+        )
+    }
+
+    fn location(&self) -> String {
+        match &self.file {
+            Some(file) => format!("{}:{}:{}", file, self.line, self.column),
+            None => format!("{}:{}", self.line, self.column),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(f, "{}: {}", self.location(), self.message)
+        } else {
+            write!(f, "{}: {} `{}`", self.location(), self.message, self.token)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses a token that is a slice of `line`, pointing at it on failure
+pub fn parse_token<T>(line: &str, token: &str) -> ParseResult<T>
+where
+    T: std::str::FromStr,
+{
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::new(line, token, "expected a number"))
+}
+
+/// What the binaries print, parse errors get the caret treatment and everything else just the message
+pub fn render_error(error: &(dyn Error + 'static), file: &str) -> String {
+    match error.downcast_ref::<ParseError>() {
+        Some(parse_error) if parse_error.file.is_none() => {
+            parse_error.clone().in_file(file).render()
+        }
+        Some(parse_error) => parse_error.render(),
+        None => format!("error: {}", error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = "Card 1: 41 x8 | 83";
+        let token = line.split_whitespace().nth(3).unwrap();
+
+        let error = ParseError::new(line, token, "expected a number").on_line(3);
+        assert_eq!(error.column, 12);
+        assert_eq!(error.to_string(), "3:12: expected a number `x8`");
+
+        // Tokens that aren't slices of the line are looked up instead
+        let error = ParseError::new(line, "83", "unexpected");
+        assert_eq!(error.column, 17);
+
+        let error = ParseError::at_end(line, "missing bid");
+        assert_eq!(error.column, line.len() + 1);
+    }
+
+    #[test]
+    fn test_render() {
+        let line = "Game 1: 3 blue, 4 rde";
+        let error = ParseError::new(line, &line[18..], "unknown color")
+            .on_line(12)
+            .in_file("input.txt");
+
+        let expected = "\
+error: unknown color
+  --> input.txt:12:19
+   |
+12 | Game 1: 3 blue, 4 rde
+   |                   ^^^";
+        assert_eq!(error.render(), expected);
+    }
+
+    #[test]
+    fn test_render_error() {
+        let line = "AAA = (BBB CCC)";
+        let error: Box<dyn Error> = Box::new(ParseError::new(line, "BBB", "oops").on_line(1));
+        assert!(render_error(error.as_ref(), "test.txt").contains("--> test.txt:1:8"));
+
+        let error: Box<dyn Error> = "plain".into();
+        assert_eq!(render_error(error.as_ref(), "test.txt"), "error: plain");
+    }
+}
@@ -0,0 +1,105 @@
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+mod error;
+mod rng;
+mod solver;
+mod trie;
+
+pub use error::{parse_token, render_error, ParseError, ParseResult};
+pub use rng::Rng;
+pub use solver::{Answer, Part, Registration, Solver};
+pub use trie::{Stats, Trie};
+
+/// Result type shared by every day, since all of them just bubble errors up to `main`
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Whole puzzle input as one string, which is what `Solver::parse` takes
+pub fn read_input<P>(filename: P) -> io::Result<String>
+where
+    P: AsRef<Path>,
+{
+    fs::read_to_string(filename)
+}
+
+/// Same as `read_input` for anything readable, e.g. stdin or an in-memory buffer
+pub fn read_from<R>(mut reader: R) -> io::Result<String>
+where
+    R: Read,
+{
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(input)
+}
+
+/// Parses every whitespace separated token that is a valid `T`, skipping the rest
+/// E.g. "Time:      7  15   30" gives 7, 15 and 30
+pub fn numbers<T>(line: &str) -> impl Iterator<Item = T> + '_
+where
+    T: FromStr,
+{
+    line.split_whitespace()
+        .filter_map(|value| value.parse::<T>().ok())
+}
+
+/// Quotes a string, escaping everything JSON doesn't allow inside one
+pub fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        let parsed: Vec<i64> = numbers("seeds: 79 14 55 13").collect();
+        assert_eq!(parsed, vec![79, 14, 55, 13]);
+
+        let parsed: Vec<f64> = numbers("Time:      7  15   30").collect();
+        assert_eq!(parsed, vec![7.0, 15.0, 30.0]);
+
+        let parsed: Vec<u32> = numbers("no numbers here").collect();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_read_input() {
+        let input = read_input("Cargo.toml").unwrap();
+        assert_eq!(input.lines().next().unwrap(), "[package]");
+
+        assert!(read_input("does-not-exist.txt").is_err());
+    }
+
+    #[test]
+    fn test_read_from() {
+        let input = read_from("Time: 7 15\nDistance: 9 40".as_bytes()).unwrap();
+        assert_eq!(input.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(
+            json_string("say \"hi\"\\\n\u{1}"),
+            "\"say \\\"hi\\\"\\\\\\n\\u0001\""
+        );
+    }
+}
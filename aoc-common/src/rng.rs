@@ -0,0 +1,70 @@
+use std::ops::RangeInclusive;
+
+/// xorshift64, the same seed always gives the same numbers,
+/// plenty for tests and simulations and keeps the workspace dependency free
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // Zero is the one state xorshift never leaves
+        Rng(if seed == 0 {
+            0x9E37_79B9_7F4A_7C15
+        } else {
+            seed
+        })
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Somewhere in `0..n`, `n` has to be positive
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// Somewhere in `range`, which can't be empty
+    pub fn range(&mut self, range: RangeInclusive<u32>) -> u32 {
+        let (start, end) = range.into_inner();
+        start + (self.next_u64() % (u64::from(end - start) + 1)) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let first: Vec<u64> = (0..5).map(|_| Rng::new(7).next_u64()).collect();
+        assert!(first.windows(2).all(|pair| pair[0] == pair[1]));
+
+        let mut rng = Rng::new(7);
+        let sequence: Vec<u64> = (0..5).map(|_| rng.next_u64()).collect();
+        assert_ne!(sequence[0], sequence[1]);
+
+        let mut zero = Rng::new(0);
+        assert_ne!(zero.next_u64(), 0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut rng = Rng::new(2023);
+
+        for _ in 0..1000 {
+            assert!(rng.below(3) < 3);
+            assert!((5..=8).contains(&rng.range(5..=8)));
+        }
+        assert_eq!(rng.range(4..=4), 4);
+
+        let mut seen = [false; 4];
+        for _ in 0..100 {
+            seen[rng.range(0..=3) as usize] = true;
+        }
+        assert!(seen.iter().all(|&seen| seen));
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::io::Read;
+
+use crate::{read_from, Result};
+
+/// Answer to a single part, every day converts its own integer type into this
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Number(value.into())
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Number(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        // Keep the exact digits if the value doesn't fit instead of wrapping around
+        match i64::try_from(value) {
+            Ok(value) => Answer::Number(value),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// Common entry point of every day
+/// `parse` turns the puzzle input into whatever the day needs, both parts then run on the parsed value
+pub trait Solver {
+    fn parse(input: &str) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// For input that isn't a file, e.g. stdin
+    fn from_reader<R>(reader: R) -> Result<Self>
+    where
+        R: Read,
+        Self: Sized,
+    {
+        Self::parse(&read_from(reader)?)
+    }
+
+    fn part_one(&self) -> Result<Answer>;
+
+    fn part_two(&self) -> Result<Answer>;
+
+    /// Anything suspicious about the input that didn't stop `part` from being solved
+    fn warnings(&self, _part: Part) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn solve(&self, part: Part) -> Result<Answer> {
+        match part {
+            Part::One => self.part_one(),
+            Part::Two => self.part_two(),
+        }
+    }
+}
+
+/// Type erased constructor of a day's solver, so tooling can keep all days in one list
+pub struct Registration {
+    pub day: u8,
+    pub parse: fn(&str) -> Result<Box<dyn Solver>>,
+}
+
+impl Registration {
+    pub const fn new<S>(day: u8) -> Self
+    where
+        S: Solver + 'static,
+    {
+        Registration {
+            day,
+            parse: parse_boxed::<S>,
+        }
+    }
+}
+
+fn parse_boxed<S>(input: &str) -> Result<Box<dyn Solver>>
+where
+    S: Solver + 'static,
+{
+    Ok(Box::new(S::parse(input)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum {
+        numbers: Vec<u32>,
+    }
+
+    impl Solver for Sum {
+        fn parse(input: &str) -> Result<Self> {
+            let numbers = crate::numbers(input).collect();
+            Ok(Sum { numbers })
+        }
+
+        fn part_one(&self) -> Result<Answer> {
+            Ok(self.numbers.iter().sum::<u32>().into())
+        }
+
+        fn part_two(&self) -> Result<Answer> {
+            Ok(self.numbers.iter().product::<u32>().into())
+        }
+    }
+
+    #[test]
+    fn test_registration() {
+        let registration = Registration::new::<Sum>(1);
+        let solver = (registration.parse)("2 3 4").unwrap();
+
+        assert_eq!(solver.solve(Part::One).unwrap(), Answer::Number(9));
+        assert_eq!(solver.solve(Part::Two).unwrap(), Answer::Number(24));
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let solver = Sum::from_reader("2 3\n4".as_bytes()).unwrap();
+        assert_eq!(solver.part_one().unwrap(), Answer::Number(9));
+    }
+
+    #[test]
+    fn test_answer_conversion() {
+        assert_eq!(Answer::from(-3_i32), Answer::Number(-3));
+        assert_eq!(Answer::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(
+            Answer::from(14616363770447_u64).to_string(),
+            "14616363770447"
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Prefix tree over sequences of `K`, e.g. the chars of a word
+/// Children are kept sorted, so iteration and printing are deterministic
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trie<K, V> {
+    root: Node<K, V>,
+    len: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node<K, V> {
+    children: BTreeMap<K, Node<K, V>>,
+    value: Option<V>,
+}
+
+/// Shape of a trie, mostly to see how much sharing the prefixes get
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub keys: usize,
+    /// Root included
+    pub nodes: usize,
+    /// Nodes without children
+    pub leaves: usize,
+    /// Length of the longest key
+    pub depth: usize,
+}
+
+impl<K, V> Node<K, V> {
+    fn new() -> Self {
+        Node {
+            children: BTreeMap::new(),
+            value: None,
+        }
+    }
+}
+
+impl<K, V> Default for Trie<K, V> {
+    fn default() -> Self {
+        Trie {
+            root: Node::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<K, V> Trie<K, V>
+where
+    K: Ord + Clone,
+{
+    pub fn new() -> Self {
+        Trie::default()
+    }
+
+    /// Returns the old value if the key was already there
+    pub fn insert<I>(&mut self, key: I, value: V) -> Option<V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut node = &mut self.root;
+        for k in key {
+            node = node.children.entry(k).or_insert_with(Node::new);
+        }
+
+        let old = node.value.replace(value);
+        if old.is_none() {
+            self.len += 1;
+        }
+        old
+    }
+
+    fn node<I>(&self, key: I) -> Option<&Node<K, V>>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut node = &self.root;
+        for k in key {
+            node = node.children.get(&k)?;
+        }
+        Some(node)
+    }
+
+    pub fn get<I>(&self, key: I) -> Option<&V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.node(key)?.value.as_ref()
+    }
+
+    pub fn get_mut<I>(&mut self, key: I) -> Option<&mut V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut node = &mut self.root;
+        for k in key {
+            node = node.children.get_mut(&k)?;
+        }
+        node.value.as_mut()
+    }
+
+    pub fn contains_key<I>(&self, key: I) -> bool
+    where
+        I: IntoIterator<Item = K>,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Removes the key and every node that only existed for it
+    pub fn remove<I>(&mut self, key: I) -> Option<V>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let key: Vec<K> = key.into_iter().collect();
+        let removed = Trie::remove_from(&mut self.root, &key);
+        if removed.is_some() {
+            self.len -= 1;
+        }
+        removed
+    }
+
+    fn remove_from(node: &mut Node<K, V>, key: &[K]) -> Option<V> {
+        let (first, rest) = match key.split_first() {
+            Some(split) => split,
+            None => return node.value.take(),
+        };
+
+        let child = node.children.get_mut(first)?;
+        let removed = Trie::remove_from(child, rest);
+
+        if child.value.is_none() && child.children.is_empty() {
+            node.children.remove(first);
+        }
+        removed
+    }
+
+    /// Longest stored key that `key` starts with, as its length and value
+    pub fn longest_prefix<I>(&self, key: I) -> Option<(usize, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut node = &self.root;
+        let mut longest = node.value.as_ref().map(|value| (0, value));
+
+        for (i, k) in key.into_iter().enumerate() {
+            node = match node.children.get(&k) {
+                Some(child) => child,
+                None => break,
+            };
+            if let Some(value) = &node.value {
+                longest = Some((i + 1, value));
+            }
+        }
+
+        longest
+    }
+
+    /// Every key that starts with `prefix` and its value, in key order
+    pub fn iter_prefix<I>(&self, prefix: I) -> impl Iterator<Item = (Vec<K>, &V)>
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut key: Vec<K> = prefix.into_iter().collect();
+        let mut entries = Vec::new();
+
+        if let Some(node) = self.node(key.clone()) {
+            Trie::collect(node, &mut key, &mut entries);
+        }
+        entries.into_iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Vec<K>, &V)> {
+        self.iter_prefix(Vec::new())
+    }
+
+    fn collect<'a>(node: &'a Node<K, V>, key: &mut Vec<K>, entries: &mut Vec<(Vec<K>, &'a V)>) {
+        if let Some(value) = &node.value {
+            entries.push((key.clone(), value));
+        }
+
+        for (k, child) in &node.children {
+            key.push(k.clone());
+            Trie::collect(child, key, entries);
+            key.pop();
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            keys: self.len,
+            nodes: 0,
+            leaves: 0,
+            depth: 0,
+        };
+
+        let mut stack = vec![(&self.root, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            stats.nodes += 1;
+            if node.children.is_empty() {
+                stats.leaves += 1;
+            }
+            if node.value.is_some() {
+                stats.depth = stats.depth.max(depth);
+            }
+
+            stack.extend(node.children.values().map(|child| (child, depth + 1)));
+        }
+
+        stats
+    }
+}
+
+impl<K, V> Trie<K, V>
+where
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    fn fmt_children(node: &Node<K, V>, indent: &str, f: &mut fmt::Formatter) -> fmt::Result {
+        let count = node.children.len();
+
+        for (i, (k, child)) in node.children.iter().enumerate() {
+            let last = i + 1 == count;
+            let branch = if last { "└── " } else { "├── " };
+
+            write!(f, "\n{}{}{}", indent, branch, k)?;
+            if let Some(value) = &child.value {
+                write!(f, " = {}", value)?;
+            }
+
+            let indent = format!("{}{}", indent, if last { "    " } else { "│   " });
+            Trie::fmt_children(child, &indent, f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The whole tree, one node per line
+impl<K, V> fmt::Display for Trie<K, V>
+where
+    K: fmt::Display,
+    V: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "·")?;
+        if let Some(value) = &self.root.value {
+            write!(f, " = {}", value)?;
+        }
+        Trie::fmt_children(&self.root, "", f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits() -> Trie<char, i32> {
+        let mut trie = Trie::new();
+        for (word, value) in [("one", 1), ("two", 2), ("three", 3), ("on", 0)] {
+            trie.insert(word.chars(), value);
+        }
+        trie
+    }
+
+    #[test]
+    fn test_insert_and_get() {
+        let mut trie = digits();
+
+        assert_eq!(trie.len(), 4);
+        assert_eq!(trie.get("one".chars()), Some(&1));
+        assert_eq!(trie.get("on".chars()), Some(&0));
+        assert_eq!(trie.get("o".chars()), None);
+        assert_eq!(trie.get("ones".chars()), None);
+        assert!(trie.contains_key("three".chars()));
+
+        assert_eq!(trie.insert("one".chars(), 11), Some(1));
+        assert_eq!(trie.len(), 4);
+
+        *trie.get_mut("two".chars()).unwrap() += 20;
+        assert_eq!(trie.get("two".chars()), Some(&22));
+
+        // Any ordered key type works
+        let mut numbers: Trie<u8, &str> = Trie::new();
+        numbers.insert([1, 2], "twelve");
+        assert_eq!(numbers.get([1, 2]), Some(&"twelve"));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut trie = digits();
+
+        assert_eq!(trie.remove("on".chars()), Some(0));
+        assert_eq!(trie.remove("on".chars()), None);
+        assert_eq!(trie.get("one".chars()), Some(&1));
+
+        assert_eq!(trie.remove("three".chars()), Some(3));
+        assert_eq!(trie.remove("tw".chars()), None);
+        assert_eq!(trie.len(), 2);
+        // "thre" went away with "three", "t" is still needed by "two"
+        assert_eq!(trie.stats().nodes, 7);
+    }
+
+    #[test]
+    fn test_longest_prefix() {
+        let trie = digits();
+
+        assert_eq!(trie.longest_prefix("oneight".chars()), Some((3, &1)));
+        assert_eq!(trie.longest_prefix("onx".chars()), Some((2, &0)));
+        assert_eq!(trie.longest_prefix("xone".chars()), None);
+    }
+
+    #[test]
+    fn test_iter_prefix() {
+        let trie = digits();
+
+        let words: Vec<(String, i32)> = trie
+            .iter_prefix("t".chars())
+            .map(|(key, value)| (key.into_iter().collect(), *value))
+            .collect();
+        assert_eq!(
+            words,
+            vec![(String::from("three"), 3), (String::from("two"), 2)]
+        );
+
+        assert_eq!(trie.iter().count(), 4);
+        assert_eq!(trie.iter_prefix("x".chars()).count(), 0);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = digits().stats();
+
+        assert_eq!(
+            stats,
+            Stats {
+                keys: 4,
+                nodes: 11,
+                leaves: 3,
+                depth: 5,
+            }
+        );
+        assert_eq!(Trie::<char, i32>::new().stats().depth, 0);
+    }
+
+    #[test]
+    fn test_display() {
+        let expected = "\
+·
+├── o
+│   └── n = 0
+│       └── e = 1
+└── t
+    ├── h
+    │   └── r
+    │       └── e
+    │           └── e = 3
+    └── w
+        └── o = 2";
+        assert_eq!(digits().to_string(), expected);
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Part, Registration, Result};
+
+/// Median parse and solve times of one part over all runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub day: u8,
+    pub part: Part,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Measurement {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Comparison {
+    New,
+    Within(f64),
+    Faster(f64),
+    Slower(f64),
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Comparison::New => write!(f, "no baseline"),
+            Comparison::Within(change) => write!(f, "{:+.1}%", change),
+            Comparison::Faster(change) => write!(f, "{:+.1}%, faster", change),
+            Comparison::Slower(change) => write!(f, "{:+.1}%, SLOWER", change),
+        }
+    }
+}
+
+fn median(mut durations: Vec<Duration>) -> Duration {
+    durations.sort();
+    durations[durations.len() / 2]
+}
+
+/// Parses the input and solves the part `runs` times, timing both phases separately
+pub fn measure(
+    registration: &Registration,
+    part: Part,
+    input: &str,
+    runs: usize,
+) -> Result<Measurement> {
+    let mut parse_times = Vec::with_capacity(runs);
+    let mut solve_times = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        let solver = (registration.parse)(input)?;
+        parse_times.push(start.elapsed());
+
+        let start = Instant::now();
+        solver.solve(part)?;
+        solve_times.push(start.elapsed());
+    }
+
+    Ok(Measurement {
+        day: registration.day,
+        part,
+        parse: median(parse_times),
+        solve: median(solve_times),
+    })
+}
+
+/// Compares total times, anything slower than `threshold` percent is a regression
+pub fn compare(
+    current: &Measurement,
+    baseline: Option<&Measurement>,
+    threshold: f64,
+) -> Comparison {
+    let baseline = match baseline {
+        Some(baseline) => baseline,
+        None => return Comparison::New,
+    };
+
+    let before = baseline.total().as_nanos() as f64;
+    let after = current.total().as_nanos() as f64;
+    if before == 0.0 {
+        return Comparison::New;
+    }
+
+    let change = (after - before) * 100.0 / before;
+    if change > threshold {
+        Comparison::Slower(change)
+    } else if change < -threshold {
+        Comparison::Faster(change)
+    } else {
+        Comparison::Within(change)
+    }
+}
+
+/// Baseline lines look like `<day> <part> <parse ns> <solve ns>`
+pub fn parse_baseline(baseline: &str) -> Result<Vec<Measurement>> {
+    let mut measurements = Vec::new();
+
+    for (i, line) in baseline.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 4 {
+            return Err(
+                format!("Line {}: expected 4 fields, found {}", i + 1, fields.len()).into(),
+            );
+        }
+
+        let part = match fields[1] {
+            "1" => Part::One,
+            "2" => Part::Two,
+            other => return Err(format!("Line {}: unknown part {}", i + 1, other).into()),
+        };
+
+        measurements.push(Measurement {
+            day: fields[0].parse()?,
+            part,
+            parse: Duration::from_nanos(fields[2].parse()?),
+            solve: Duration::from_nanos(fields[3].parse()?),
+        });
+    }
+
+    Ok(measurements)
+}
+
+pub fn format_baseline(measurements: &[Measurement]) -> String {
+    let mut baseline = String::from("# day part parse-ns solve-ns\n");
+
+    for measurement in measurements {
+        baseline.push_str(&format!(
+            "{} {} {} {}\n",
+            measurement.day,
+            measurement.part,
+            measurement.parse.as_nanos(),
+            measurement.solve.as_nanos()
+        ));
+    }
+
+    baseline
+}
+
+/// Replaces the baseline entries that were measured again and keeps the rest
+pub fn merge(baseline: &mut Vec<Measurement>, current: &[Measurement]) {
+    for measurement in current {
+        match baseline
+            .iter_mut()
+            .find(|old| old.day == measurement.day && old.part == measurement.part)
+        {
+            Some(old) => *old = measurement.clone(),
+            None => baseline.push(measurement.clone()),
+        }
+    }
+
+    baseline.sort_by_key(|measurement| (measurement.day, measurement.part));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    fn measurement(day: u8, part: Part, parse: u64, solve: u64) -> Measurement {
+        Measurement {
+            day,
+            part,
+            parse: Duration::from_nanos(parse),
+            solve: Duration::from_nanos(solve),
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let input = include_str!("../../day-4/test.txt");
+        let measurement = measure(find(4).unwrap(), Part::Two, input, 3).unwrap();
+
+        assert_eq!(measurement.day, 4);
+        assert_eq!(measurement.part, Part::Two);
+    }
+
+    #[test]
+    fn test_compare() {
+        let baseline = measurement(1, Part::One, 100, 900);
+
+        assert_eq!(
+            compare(&measurement(1, Part::One, 100, 900), None, 10.0),
+            Comparison::New
+        );
+        assert_eq!(
+            compare(&measurement(1, Part::One, 100, 950), Some(&baseline), 10.0),
+            Comparison::Within(5.0)
+        );
+        assert_eq!(
+            compare(&measurement(1, Part::One, 200, 1300), Some(&baseline), 10.0),
+            Comparison::Slower(50.0)
+        );
+        assert_eq!(
+            compare(&measurement(1, Part::One, 100, 400), Some(&baseline), 10.0),
+            Comparison::Faster(-50.0)
+        );
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let measurements = vec![
+            measurement(1, Part::One, 10, 20),
+            measurement(5, Part::Two, 30, 4_000_000_000),
+        ];
+        let baseline = format_baseline(&measurements);
+
+        assert_eq!(parse_baseline(&baseline).unwrap(), measurements);
+        assert!(parse_baseline("1 3 10 20").is_err());
+        assert!(parse_baseline("1 1 10").is_err());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut baseline = vec![
+            measurement(2, Part::One, 10, 20),
+            measurement(1, Part::Two, 10, 20),
+        ];
+        merge(
+            &mut baseline,
+            &[
+                measurement(2, Part::One, 1, 2),
+                measurement(1, Part::One, 3, 4),
+            ],
+        );
+
+        assert_eq!(
+            baseline,
+            vec![
+                measurement(1, Part::One, 3, 4),
+                measurement(1, Part::Two, 10, 20),
+                measurement(2, Part::One, 1, 2),
+            ]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use aoc_common::{Part, Registration, Result};
+
+use crate::verify::Outcome;
+
+/// A fenced block of a README together with the answers annotated right after it
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    /// Line of the opening fence, to point at the block when it fails
+    pub line: usize,
+    pub input: String,
+    pub answers: Vec<(Part, String)>,
+}
+
+/// Parses `<!-- example part <1|2>: <answer> -->`, anything else is not an annotation
+fn parse_annotation(line: &str) -> Option<Result<(Part, String)>> {
+    let comment = line.trim().strip_prefix("<!--")?.strip_suffix("-->")?;
+    let annotation = comment.trim().strip_prefix("example")?;
+
+    let parsed = match annotation.trim().strip_prefix("part") {
+        Some(rest) => match rest.split_once(':') {
+            Some((part, answer)) if !answer.trim().is_empty() => match part.trim() {
+                "1" => Ok((Part::One, answer.trim().to_owned())),
+                "2" => Ok((Part::Two, answer.trim().to_owned())),
+                other => Err(format!("unknown part {}", other).into()),
+            },
+            _ => Err("expected `part <N>: <answer>`".into()),
+        },
+        None => Err("expected `part <N>: <answer>`".into()),
+    };
+    Some(parsed)
+}
+
+/// Every fenced block that has at least one annotation, blocks without one are just illustrations
+/// Annotations belong to the closest block above them
+pub fn extract(readme: &str) -> Result<Vec<Example>> {
+    let mut examples: Vec<Example> = Vec::new();
+    let mut block: Option<(usize, Vec<&str>)> = None;
+
+    for (i, line) in readme.lines().enumerate() {
+        if let Some((start, lines)) = &mut block {
+            if line.trim() == "```" {
+                let mut input = lines.join("\n");
+                input.push('\n');
+                examples.push(Example {
+                    line: *start,
+                    input,
+                    answers: Vec::new(),
+                });
+                block = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if line.trim_start().starts_with("```") {
+            block = Some((i + 1, Vec::new()));
+            continue;
+        }
+
+        if let Some(annotation) = parse_annotation(line) {
+            let annotation = annotation.map_err(|e| format!("Line {}: {}", i + 1, e))?;
+            match examples.last_mut() {
+                Some(example) => example.answers.push(annotation),
+                None => return Err(format!("Line {}: annotation before any block", i + 1).into()),
+            }
+        }
+    }
+
+    if let Some((start, _)) = block {
+        return Err(format!("Line {}: block is never closed", start).into());
+    }
+
+    examples.retain(|example| !example.answers.is_empty());
+    Ok(examples)
+}
+
+pub fn check(
+    registration: &Registration,
+    example: &Example,
+    part: Part,
+    expected: &str,
+) -> Outcome {
+    let answer = (registration.parse)(&example.input).and_then(|solver| solver.solve(part));
+
+    match answer {
+        Ok(answer) if answer.to_string() == expected => Outcome::Pass,
+        Ok(answer) => Outcome::Mismatch(answer.to_string()),
+        Err(e) => Outcome::Fail(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::REGISTRY;
+    use aoc_common::read_input;
+
+    #[test]
+    fn test_extract() {
+        let readme = "\
+# Day 0
+``` text
+1 2
+3
+```
+<!-- example part 1: 6 -->
+<!-- example part 2: 6 -->
+
+```
+not an example
+```
+";
+        let expected = Example {
+            line: 2,
+            input: String::from("1 2\n3\n"),
+            answers: vec![
+                (Part::One, String::from("6")),
+                (Part::Two, String::from("6")),
+            ],
+        };
+        assert_eq!(extract(readme).unwrap(), vec![expected]);
+
+        assert!(extract("<!-- example part 1: 6 -->").is_err());
+        assert!(extract("```\n1\n```\n<!-- example part 3: 6 -->").is_err());
+        assert!(extract("```\n1\n```\n<!-- example part 1 -->").is_err());
+        assert!(extract("```\n1\n").is_err());
+        assert!(extract("```\n1\n```\n<!-- a regular comment -->")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_readme_examples() {
+        // Generated from every README, a new day is picked up as soon as its examples are annotated
+        for registration in REGISTRY {
+            let path = format!(
+                "{}/../day-{}/README.md",
+                env!("CARGO_MANIFEST_DIR"),
+                registration.day
+            );
+            let readme = read_input(&path).unwrap();
+
+            for example in extract(&readme).unwrap() {
+                for (part, expected) in &example.answers {
+                    let outcome = check(registration, &example, *part, expected);
+                    assert_eq!(
+                        outcome,
+                        Outcome::Pass,
+                        "{} line {} part {}",
+                        path,
+                        example.line,
+                        part
+                    );
+                }
+            }
+        }
+    }
+}
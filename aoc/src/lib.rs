@@ -0,0 +1,51 @@
+use aoc_common::Registration;
+
+pub mod bench;
+pub mod examples;
+pub mod output;
+pub mod scaffold;
+pub mod verify;
+
+/// Every solved day, in order
+pub const REGISTRY: &[Registration] = &[
+    Registration::new::<day_1::Solution>(1),
+    Registration::new::<day_2::Solution>(2),
+    Registration::new::<day_3::Solution>(3),
+    Registration::new::<day_4::Solution>(4),
+    Registration::new::<day_5::Solution>(5),
+    Registration::new::<day_6::Solution>(6),
+    Registration::new::<day_7::Solution>(7),
+    Registration::new::<day_8::Solution>(8),
+];
+
+pub fn find(day: u8) -> Option<&'static Registration> {
+    REGISTRY.iter().find(|registration| registration.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Answer, Part};
+
+    #[test]
+    fn test_registry() {
+        let days: Vec<u8> = REGISTRY
+            .iter()
+            .map(|registration| registration.day)
+            .collect();
+        assert_eq!(days[..8], (1..=8).collect::<Vec<u8>>());
+        // New days are scaffolded into place, the registry has to stay sorted
+        assert!(days.windows(2).all(|pair| pair[0] < pair[1]));
+
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn test_registered_solver() {
+        let input = include_str!("../../day-7/test.txt");
+        let solver = (find(7).unwrap().parse)(input).unwrap();
+
+        assert_eq!(solver.solve(Part::One).unwrap(), Answer::Number(6440));
+        assert_eq!(solver.solve(Part::Two).unwrap(), Answer::Number(5905));
+    }
+}
@@ -0,0 +1,606 @@
+use std::env;
+use std::io;
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+use aoc::bench::{self, Comparison, Measurement};
+use aoc::examples;
+use aoc::output::{self, PartReport};
+use aoc::scaffold;
+use aoc::verify::{self, Outcome, Report};
+use aoc_common::{read_from, read_input, render_error, Part, Result};
+
+const USAGE: &str = "Usage:
+    aoc --day <N> [--part <1|2|both>] [--input <path or - for stdin>] [--format <text|json>]
+    aoc verify [--answers <path>] [--day <N>]
+    aoc bench [--day <N>] [--part <1|2|both>] [--runs <N>] [--baseline <path>] [--threshold <percent>] [--save]
+    aoc examples [--day <N>]
+    aoc new --day <N>";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run(Args),
+    Verify { answers: String, day: Option<u8> },
+    Bench(BenchArgs),
+    Examples { day: Option<u8> },
+    New { day: u8 },
+}
+
+#[derive(Debug, PartialEq)]
+struct BenchArgs {
+    day: Option<u8>,
+    parts: Parts,
+    runs: usize,
+    baseline: String,
+    threshold: f64,
+    save: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum Parts {
+    One,
+    Two,
+    Both,
+}
+
+impl Parts {
+    fn to_vec(&self) -> Vec<Part> {
+        match self {
+            Parts::One => vec![Part::One],
+            Parts::Two => vec![Part::Two],
+            Parts::Both => vec![Part::One, Part::Two],
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: u8,
+    parts: Parts,
+    input: String,
+    format: Format,
+}
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn parse_command<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("verify") => {
+            args.next();
+            parse_verify_args(args)
+        }
+        Some("bench") => {
+            args.next();
+            Ok(Command::Bench(parse_bench_args(args)?))
+        }
+        Some("examples") => {
+            args.next();
+            parse_examples_args(args)
+        }
+        Some("new") => {
+            args.next();
+            parse_new_args(args)
+        }
+        _ => Ok(Command::Run(parse_args(args)?)),
+    }
+}
+
+fn parse_verify_args<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut answers = String::from("answers.txt");
+    let mut day = None;
+
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let value = match args.next() {
+            Some(value) => value,
+            None => return Err(format!("Missing value for {}", flag).into()),
+        };
+
+        match flag.as_str() {
+            "--answers" => answers = value,
+            "--day" => day = Some(value.parse::<u8>()?),
+            _ => return Err(format!("Unknown flag {}", flag).into()),
+        }
+    }
+
+    Ok(Command::Verify { answers, day })
+}
+
+fn parse_examples_args<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut day = None;
+
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let value = match args.next() {
+            Some(value) => value,
+            None => return Err(format!("Missing value for {}", flag).into()),
+        };
+
+        match flag.as_str() {
+            "--day" => day = Some(value.parse::<u8>()?),
+            _ => return Err(format!("Unknown flag {}", flag).into()),
+        }
+    }
+
+    Ok(Command::Examples { day })
+}
+
+fn parse_new_args<I>(args: I) -> Result<Command>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut day = None;
+
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let value = match args.next() {
+            Some(value) => value,
+            None => return Err(format!("Missing value for {}", flag).into()),
+        };
+
+        match flag.as_str() {
+            "--day" => day = Some(value.parse::<u8>()?),
+            _ => return Err(format!("Unknown flag {}", flag).into()),
+        }
+    }
+
+    let day = day.ok_or("--day is required")?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {}", day).into());
+    }
+
+    Ok(Command::New { day })
+}
+
+fn parse_bench_args<I>(args: I) -> Result<BenchArgs>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut bench_args = BenchArgs {
+        day: None,
+        parts: Parts::Both,
+        runs: 10,
+        baseline: String::from("bench-baseline.txt"),
+        threshold: 10.0,
+        save: false,
+    };
+
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        // The only flag without a value
+        if flag == "--save" {
+            bench_args.save = true;
+            continue;
+        }
+
+        let value = match args.next() {
+            Some(value) => value,
+            None => return Err(format!("Missing value for {}", flag).into()),
+        };
+
+        match flag.as_str() {
+            "--day" => bench_args.day = Some(value.parse::<u8>()?),
+            "--part" => bench_args.parts = parse_parts(&value)?,
+            "--runs" => bench_args.runs = value.parse()?,
+            "--baseline" => bench_args.baseline = value,
+            "--threshold" => bench_args.threshold = value.parse()?,
+            _ => return Err(format!("Unknown flag {}", flag).into()),
+        }
+    }
+
+    Ok(bench_args)
+}
+
+fn parse_parts(value: &str) -> Result<Parts> {
+    match value {
+        "1" => Ok(Parts::One),
+        "2" => Ok(Parts::Two),
+        "both" => Ok(Parts::Both),
+        _ => Err(format!("Unknown part {}", value).into()),
+    }
+}
+
+fn parse_args<I>(args: I) -> Result<Args>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut day = None;
+    let mut parts = Parts::Both;
+    let mut input = None;
+    let mut format = Format::Text;
+
+    let mut args = args.into_iter();
+    while let Some(flag) = args.next() {
+        let value = match args.next() {
+            Some(value) => value,
+            None => return Err(format!("Missing value for {}", flag).into()),
+        };
+
+        match flag.as_str() {
+            "--day" => day = Some(value.parse::<u8>()?),
+            "--part" => parts = parse_parts(&value)?,
+            "--input" => input = Some(value),
+            "--format" => {
+                format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("Unknown format {}", value).into()),
+                }
+            }
+            _ => return Err(format!("Unknown flag {}", flag).into()),
+        }
+    }
+
+    let day = day.ok_or("--day is required")?;
+    // Runs from the workspace root by default, where every day keeps its own input
+    let input = input.unwrap_or_else(|| format!("day-{}/input.txt", day));
+
+    Ok(Args {
+        day,
+        parts,
+        input,
+        format,
+    })
+}
+
+/// `-` reads the puzzle input from stdin
+fn load_input(path: &str) -> io::Result<String> {
+    match path {
+        "-" => read_from(io::stdin().lock()),
+        path => read_input(path),
+    }
+}
+
+fn run(day: u8, part: Part, input: &str) -> Result<PartReport> {
+    let registration = match aoc::find(day) {
+        Some(registration) => registration,
+        None => return Err(format!("Day {} is not solved yet", day).into()),
+    };
+
+    Ok(output::run_part(registration, part, input))
+}
+
+fn print_text(day: u8, input: &str, reports: &[PartReport]) {
+    for report in reports {
+        for warning in &report.warnings {
+            eprintln!("warning: day {} part {}: {}", day, report.part, warning);
+        }
+
+        match &report.answer {
+            Ok(answer) => println!("Day {} part {}: {}", day, report.part, answer),
+            Err(e) => {
+                eprintln!("Day {} part {} failed", day, report.part);
+                eprintln!("{}", render_error(e.as_ref(), input));
+            }
+        }
+    }
+}
+
+fn run_verify(answers: &str, day: Option<u8>) -> Result<bool> {
+    let manifest = verify::parse_manifest(&read_input(answers)?)?;
+
+    let mut checked = 0;
+    let mut failed = 0;
+
+    for expected in &manifest {
+        if day.is_some_and(|day| day != expected.day) {
+            continue;
+        }
+
+        let input = format!("day-{}/input.txt", expected.day);
+        let report = match read_input(&input) {
+            Ok(input) => verify::verify(expected, &input),
+            Err(e) => Report {
+                outcome: Outcome::Fail(format!("can't read {}: {}", input, e)),
+                duration: Duration::ZERO,
+            },
+        };
+
+        println!(
+            "Day {} part {}: {} ({:.2?})",
+            expected.day, expected.part, report.outcome, report.duration
+        );
+
+        checked += 1;
+        if report.outcome != Outcome::Pass {
+            failed += 1;
+        }
+    }
+
+    println!("{} checked, {} failed", checked, failed);
+    Ok(failed == 0)
+}
+
+fn run_examples(day: Option<u8>) -> Result<bool> {
+    let mut checked = 0;
+    let mut failed = 0;
+
+    for registration in aoc::REGISTRY {
+        if day.is_some_and(|day| day != registration.day) {
+            continue;
+        }
+
+        let readme = format!("day-{}/README.md", registration.day);
+        let extracted =
+            examples::extract(&read_input(&readme)?).map_err(|e| format!("{}: {}", readme, e))?;
+
+        for example in extracted {
+            for (part, expected) in &example.answers {
+                let outcome = examples::check(registration, &example, *part, expected);
+                println!(
+                    "Day {} example at line {} part {}: {}",
+                    registration.day, example.line, part, outcome
+                );
+
+                checked += 1;
+                if outcome != Outcome::Pass {
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    println!("{} checked, {} failed", checked, failed);
+    Ok(failed == 0)
+}
+
+fn run_bench(args: &BenchArgs) -> Result<bool> {
+    // A missing baseline just means this is the first run
+    let mut baseline = match read_input(&args.baseline) {
+        Ok(baseline) => bench::parse_baseline(&baseline)?,
+        Err(_) => Vec::new(),
+    };
+
+    let mut measurements: Vec<Measurement> = Vec::new();
+    let mut regressions = 0;
+
+    for registration in aoc::REGISTRY {
+        if args.day.is_some_and(|day| day != registration.day) {
+            continue;
+        }
+
+        let input = read_input(format!("day-{}/input.txt", registration.day))?;
+
+        for part in args.parts.to_vec() {
+            let measurement = bench::measure(registration, part, &input, args.runs)?;
+            let previous = baseline
+                .iter()
+                .find(|old| old.day == measurement.day && old.part == measurement.part);
+            let comparison = bench::compare(&measurement, previous, args.threshold);
+
+            println!(
+                "Day {} part {}: parse {:.2?}, solve {:.2?} ({})",
+                measurement.day, measurement.part, measurement.parse, measurement.solve, comparison
+            );
+
+            if let Comparison::Slower(_) = comparison {
+                regressions += 1;
+            }
+            measurements.push(measurement);
+        }
+    }
+
+    if args.save {
+        bench::merge(&mut baseline, &measurements);
+        std::fs::write(&args.baseline, bench::format_baseline(&baseline))?;
+        println!("Saved baseline to {}", args.baseline);
+    }
+
+    println!(
+        "{} measured, {} slower than {}%",
+        measurements.len(),
+        regressions,
+        args.threshold
+    );
+    Ok(regressions == 0)
+}
+
+fn main() {
+    let command = match parse_command(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    let args = match command {
+        Command::Run(args) => args,
+        Command::Bench(bench_args) => match run_bench(&bench_args) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Benchmark failed: {}", e);
+                process::exit(2);
+            }
+        },
+        Command::Examples { day } => match run_examples(day) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Can't check examples: {}", e);
+                process::exit(2);
+            }
+        },
+        Command::New { day } => match scaffold::scaffold(Path::new("."), day) {
+            Ok(written) => {
+                for path in written {
+                    println!("Wrote {}", path.display());
+                }
+                return;
+            }
+            Err(e) => {
+                eprintln!("Can't create day {}: {}", day, e);
+                process::exit(1);
+            }
+        },
+        Command::Verify { answers, day } => match run_verify(&answers, day) {
+            Ok(true) => return,
+            Ok(false) => process::exit(1),
+            Err(e) => {
+                eprintln!("Can't verify answers from {}: {}", answers, e);
+                process::exit(2);
+            }
+        },
+    };
+
+    // Read once up front, stdin can't be read again for the second part
+    let input = match load_input(&args.input) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Can't read {}: {}", args.input, e);
+            process::exit(1);
+        }
+    };
+
+    let mut reports = Vec::new();
+    for part in args.parts.to_vec() {
+        match run(args.day, part, &input) {
+            Ok(report) => reports.push(report),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    match args.format {
+        Format::Text => print_text(args.day, &args.input, &reports),
+        Format::Json => println!("{}", output::to_json(args.day, &args.input, &reports)),
+    }
+
+    if reports.iter().any(|report| report.answer.is_err()) {
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Answer;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let expected = Args {
+            day: 3,
+            parts: Parts::Both,
+            input: String::from("day-3/input.txt"),
+            format: Format::Text,
+        };
+        assert_eq!(parse_args(args("--day 3")).unwrap(), expected);
+
+        let expected = Args {
+            day: 7,
+            parts: Parts::Two,
+            input: String::from("day-7/test.txt"),
+            format: Format::Json,
+        };
+        assert_eq!(
+            parse_args(args(
+                "--part 2 --day 7 --input day-7/test.txt --format json"
+            ))
+            .unwrap(),
+            expected
+        );
+
+        assert!(parse_args(args("--part 1")).is_err());
+        assert!(parse_args(args("--day 1 --part 3")).is_err());
+        assert!(parse_args(args("--day")).is_err());
+        assert!(parse_args(args("--day 1 --verbose yes")).is_err());
+        assert!(parse_args(args("--day 1 --format yaml")).is_err());
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let expected = Command::Verify {
+            answers: String::from("answers.txt"),
+            day: None,
+        };
+        assert_eq!(parse_command(args("verify")).unwrap(), expected);
+
+        let expected = Command::Verify {
+            answers: String::from("other.txt"),
+            day: Some(2),
+        };
+        assert_eq!(
+            parse_command(args("verify --day 2 --answers other.txt")).unwrap(),
+            expected
+        );
+
+        assert!(matches!(
+            parse_command(args("--day 2")).unwrap(),
+            Command::Run(_)
+        ));
+        assert!(parse_command(args("verify --part 1")).is_err());
+
+        let expected = BenchArgs {
+            day: Some(4),
+            parts: Parts::Two,
+            runs: 3,
+            baseline: String::from("bench-baseline.txt"),
+            threshold: 25.0,
+            save: true,
+        };
+        assert_eq!(
+            parse_command(args(
+                "bench --day 4 --save --part 2 --runs 3 --threshold 25"
+            ))
+            .unwrap(),
+            Command::Bench(expected)
+        );
+        assert!(parse_command(args("bench --runs")).is_err());
+
+        assert_eq!(
+            parse_command(args("new --day 9")).unwrap(),
+            Command::New { day: 9 }
+        );
+        assert!(parse_command(args("new")).is_err());
+
+        assert_eq!(
+            parse_command(args("examples --day 8")).unwrap(),
+            Command::Examples { day: Some(8) }
+        );
+        assert!(parse_command(args("examples --part 1")).is_err());
+        assert!(parse_command(args("new --day 26")).is_err());
+    }
+
+    #[test]
+    fn test_run() {
+        let input = include_str!("../../day-4/test.txt");
+
+        let answer = run(4, Part::One, input).unwrap().answer;
+        assert_eq!(answer.unwrap(), Answer::Number(13));
+        let answer = run(4, Part::Two, input).unwrap().answer;
+        assert_eq!(answer.unwrap(), Answer::Number(30));
+
+        assert!(run(25, Part::One, input).is_err());
+        let answer = run(4, Part::One, "Card 1: 41 x8 | 83").unwrap().answer;
+        assert!(answer.is_err());
+    }
+
+    #[test]
+    fn test_load_input() {
+        assert!(load_input("../day-4/missing.txt").is_err());
+    }
+}
@@ -0,0 +1,145 @@
+use std::time::{Duration, Instant};
+
+use aoc_common::{json_string, Answer, ParseError, Part, Registration, Result};
+
+/// Everything one part of a run produced, whether it succeeded or not
+pub struct PartReport {
+    pub part: Part,
+    pub parse: Duration,
+    pub solve: Duration,
+    pub answer: Result<Answer>,
+    pub warnings: Vec<String>,
+}
+
+/// Parses the input and solves `part`, timing both phases separately
+pub fn run_part(registration: &Registration, part: Part, input: &str) -> PartReport {
+    let start = Instant::now();
+    let solver = (registration.parse)(input);
+    let parse = start.elapsed();
+
+    let solver = match solver {
+        Ok(solver) => solver,
+        Err(e) => {
+            return PartReport {
+                part,
+                parse,
+                solve: Duration::ZERO,
+                answer: Err(e),
+                warnings: Vec::new(),
+            }
+        }
+    };
+
+    let start = Instant::now();
+    let answer = solver.solve(part);
+    let solve = start.elapsed();
+
+    PartReport {
+        part,
+        parse,
+        solve,
+        answer,
+        warnings: solver.warnings(part),
+    }
+}
+
+fn json_answer(answer: &Answer) -> String {
+    match answer {
+        Answer::Number(value) => value.to_string(),
+        Answer::Text(value) => json_string(value),
+    }
+}
+
+/// Parse errors keep their location so scripts don't have to parse the message
+fn json_error(error: &(dyn std::error::Error + 'static), input: &str) -> String {
+    match error.downcast_ref::<ParseError>() {
+        Some(parse_error) => format!(
+            "{{\"message\":{},\"file\":{},\"line\":{},\"column\":{},\"token\":{}}}",
+            json_string(&parse_error.message),
+            json_string(parse_error.file.as_deref().unwrap_or(input)),
+            parse_error.line,
+            parse_error.column,
+            json_string(&parse_error.token)
+        ),
+        None => format!("{{\"message\":{}}}", json_string(&error.to_string())),
+    }
+}
+
+fn json_part(report: &PartReport, input: &str) -> String {
+    let result = match &report.answer {
+        Ok(answer) => format!("\"answer\":{}", json_answer(answer)),
+        Err(e) => format!("\"error\":{}", json_error(e.as_ref(), input)),
+    };
+    let warnings: Vec<String> = report.warnings.iter().map(|w| json_string(w)).collect();
+
+    format!(
+        "{{\"part\":{},{},\"parse_ns\":{},\"solve_ns\":{},\"warnings\":[{}]}}",
+        report.part,
+        result,
+        report.parse.as_nanos(),
+        report.solve.as_nanos(),
+        warnings.join(",")
+    )
+}
+
+/// One document for the whole run, on a single line
+pub fn to_json(day: u8, input: &str, reports: &[PartReport]) -> String {
+    let parts: Vec<String> = reports
+        .iter()
+        .map(|report| json_part(report, input))
+        .collect();
+
+    format!(
+        "{{\"day\":{},\"input\":{},\"parts\":[{}]}}",
+        day,
+        json_string(input),
+        parts.join(",")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::find;
+
+    #[test]
+    fn test_run_part() {
+        let input = include_str!("../../day-4/test.txt");
+        let report = run_part(find(4).unwrap(), Part::Two, input);
+        assert_eq!(report.answer.unwrap(), Answer::Number(30));
+
+        let report = run_part(find(4).unwrap(), Part::One, "Card 1: 41 x8 | 83");
+        assert!(report.answer.is_err());
+        assert_eq!(report.solve, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let reports = vec![
+            PartReport {
+                part: Part::One,
+                parse: Duration::from_nanos(10),
+                solve: Duration::from_nanos(20),
+                answer: Ok(Answer::Number(6440)),
+                warnings: vec![String::from("line 3 has no digits")],
+            },
+            PartReport {
+                part: Part::Two,
+                parse: Duration::from_nanos(30),
+                solve: Duration::ZERO,
+                answer: Err(ParseError::new("QQ", "QQ", "bad hand").on_line(2).into()),
+                warnings: Vec::new(),
+            },
+        ];
+
+        let expected = concat!(
+            "{\"day\":7,\"input\":\"test.txt\",\"parts\":[",
+            "{\"part\":1,\"answer\":6440,\"parse_ns\":10,\"solve_ns\":20,",
+            "\"warnings\":[\"line 3 has no digits\"]},",
+            "{\"part\":2,\"error\":{\"message\":\"bad hand\",\"file\":\"test.txt\",",
+            "\"line\":2,\"column\":1,\"token\":\"QQ\"},\"parse_ns\":30,\"solve_ns\":0,",
+            "\"warnings\":[]}]}"
+        );
+        assert_eq!(to_json(7, "test.txt", &reports), expected);
+    }
+}
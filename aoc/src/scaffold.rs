@@ -0,0 +1,288 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{read_input, Result};
+
+fn manifest(day: u8) -> String {
+    format!(
+        "[package]
+name = \"day-{day}\"
+version = \"0.1.0\"
+edition = \"2021\"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = {{ path = \"../aoc-common\" }}
+"
+    )
+}
+
+fn readme(day: u8) -> String {
+    format!(
+        "# Day {day}
+Original puzzle can be found [here]
+
+# Part one
+
+# Part two
+
+[here]: https://adventofcode.com/2023/day/{day}
+"
+    )
+}
+
+fn main_rs(day: u8) -> String {
+    format!(
+        "use std::process;
+
+use aoc_common::render_error;
+
+fn main() {{
+    let filename = \"input.txt\";
+    match day_{day}::solve_part_one(filename) {{
+        Ok(answer) => println!(\"{{}}\", answer),
+        Err(e) => {{
+            eprintln!(\"{{}}\", render_error(e.as_ref(), filename));
+            process::exit(1);
+        }}
+    }}
+}}
+"
+    )
+}
+
+const LIB_RS: &str = "use aoc_common::{read_input, Answer, ParseResult, Result, Solver};
+
+pub struct Solution {
+    lines: Vec<String>,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            lines: parse_input(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(part_one(&self.lines).into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(part_two(&self.lines).into())
+    }
+}
+
+/// Line parsers return a `ParseError` pointing at the token, add the line number here
+fn parse_input(input: &str) -> ParseResult<Vec<String>> {
+    Ok(input.lines().map(String::from).collect())
+}
+
+fn part_one(_lines: &[String]) -> u32 {
+    0
+}
+
+fn part_two(_lines: &[String]) -> u32 {
+    0
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u32> {
+    Ok(part_one(&parse_input(input)?))
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u32> {
+    Ok(part_two(&parse_input(input)?))
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u32> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!(\"../test.txt\");
+
+    #[test]
+    fn test_solve_part_one() {
+        // Replace with the answer to the example once part one is solved
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_solve_part_two() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 0);
+    }
+}
+";
+
+/// Day of a line that starts with `prefix` followed by the day number, e.g. `day-7 = ...`
+fn registered_day(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+
+    rest[..end].parse().ok()
+}
+
+/// Adds `entry` to a list of per-day lines, keeping the list sorted by day
+/// `entry` is the new line without indentation, it gets the indentation of its neighbour
+pub fn register(text: &str, prefix: &str, day: u8, entry: &str) -> Result<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| registered_day(line, prefix).map(|day| (i, day)))
+        .collect();
+
+    if days.iter().any(|&(_, registered)| registered == day) {
+        return Err(format!("day {} is already registered", day).into());
+    }
+
+    // Goes after the last smaller day, or in front of the first one
+    let (neighbour, position) = match days.iter().rev().find(|&&(_, other)| other < day) {
+        Some(&(i, _)) => (i, i + 1),
+        None => match days.first() {
+            Some(&(i, _)) => (i, i),
+            None => return Err(format!("no `{}` lines to register next to", prefix).into()),
+        },
+    };
+
+    let line = lines[neighbour];
+    let indentation = &line[..line.len() - line.trim_start().len()];
+    let entry = format!("{}{}", indentation, entry);
+
+    let mut result: Vec<&str> = lines[..position].to_vec();
+    result.push(&entry);
+    result.extend(&lines[position..]);
+
+    let mut result = result.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Creates `day-N` under the workspace `root` and registers it with the workspace and the runner
+/// Returns every file that was written
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>> {
+    let directory = root.join(format!("day-{}", day));
+    if directory.exists() {
+        return Err(format!("{} already exists", directory.display()).into());
+    }
+
+    let workspace = root.join("Cargo.toml");
+    let runner = root.join("aoc").join("Cargo.toml");
+    let registry = root.join("aoc").join("src").join("lib.rs");
+
+    // Everything is checked before the first write, so a failure leaves the tree untouched
+    let registrations = vec![
+        (
+            workspace.clone(),
+            register(
+                &read_input(&workspace)?,
+                "\"day-",
+                day,
+                &format!("\"day-{}\",", day),
+            )?,
+        ),
+        (
+            runner.clone(),
+            register(
+                &read_input(&runner)?,
+                "day-",
+                day,
+                &format!("day-{} = {{ path = \"../day-{}\" }}", day, day),
+            )?,
+        ),
+        (
+            registry.clone(),
+            register(
+                &read_input(&registry)?,
+                "Registration::new::<day_",
+                day,
+                &format!("Registration::new::<day_{}::Solution>({}),", day, day),
+            )?,
+        ),
+    ];
+
+    let files = vec![
+        (directory.join("Cargo.toml"), manifest(day)),
+        (directory.join("README.md"), readme(day)),
+        (directory.join("input.txt"), String::new()),
+        (directory.join("test.txt"), String::new()),
+        (directory.join("src").join("main.rs"), main_rs(day)),
+        (directory.join("src").join("lib.rs"), String::from(LIB_RS)),
+    ];
+
+    fs::create_dir_all(directory.join("src"))?;
+
+    let mut written = Vec::new();
+    for (path, contents) in files.into_iter().chain(registrations) {
+        fs::write(&path, contents)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registered_day() {
+        assert_eq!(registered_day("    \"day-12\",", "\"day-"), Some(12));
+        assert_eq!(
+            registered_day("day-3 = { path = \"../day-3\" }", "day-"),
+            Some(3)
+        );
+        assert_eq!(
+            registered_day("aoc-common = { path = \"../aoc-common\" }", "day-"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let members = "members = [\n    \"aoc\",\n    \"day-1\",\n    \"day-3\",\n]\n";
+
+        let expected =
+            "members = [\n    \"aoc\",\n    \"day-1\",\n    \"day-2\",\n    \"day-3\",\n]\n";
+        assert_eq!(
+            register(members, "\"day-", 2, "\"day-2\",").unwrap(),
+            expected
+        );
+
+        let expected =
+            "members = [\n    \"aoc\",\n    \"day-1\",\n    \"day-3\",\n    \"day-9\",\n]\n";
+        assert_eq!(
+            register(members, "\"day-", 9, "\"day-9\",").unwrap(),
+            expected
+        );
+
+        assert!(register(members, "\"day-", 3, "\"day-3\",").is_err());
+        assert!(register("members = []", "\"day-", 3, "\"day-3\",").is_err());
+    }
+
+    #[test]
+    fn test_registry_template() {
+        let registry = include_str!("lib.rs");
+        let updated = register(
+            registry,
+            "Registration::new::<day_",
+            25,
+            "Registration::new::<day_25::Solution>(25),",
+        )
+        .unwrap();
+
+        assert!(updated.contains("    Registration::new::<day_25::Solution>(25),\n];"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use aoc_common::{Part, Result};
+
+use crate::find;
+
+/// One accepted answer from the manifest
+#[derive(Debug, PartialEq)]
+pub struct Expected {
+    pub day: u8,
+    pub part: Part,
+    pub input_hash: u64,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Pass,
+    Mismatch(String),
+    Fail(String),
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "pass"),
+            Outcome::Mismatch(actual) => write!(f, "mismatch, got {}", actual),
+            Outcome::Fail(reason) => write!(f, "fail, {}", reason),
+        }
+    }
+}
+
+/// FNV-1a, so the manifest hashes stay the same across Rust versions
+pub fn input_hash(input: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    hash
+}
+
+/// Manifest lines look like `<day> <part> <input hash in hex> <answer>`, `#` starts a comment
+pub fn parse_manifest(manifest: &str) -> Result<Vec<Expected>> {
+    let mut expected = Vec::new();
+
+    for (i, line) in manifest.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 4 {
+            return Err(
+                format!("Line {}: expected 4 fields, found {}", i + 1, fields.len()).into(),
+            );
+        }
+
+        let part = match fields[1] {
+            "1" => Part::One,
+            "2" => Part::Two,
+            other => return Err(format!("Line {}: unknown part {}", i + 1, other).into()),
+        };
+
+        expected.push(Expected {
+            day: fields[0].parse()?,
+            part,
+            input_hash: u64::from_str_radix(fields[2], 16)?,
+            answer: fields[3].to_owned(),
+        });
+    }
+
+    Ok(expected)
+}
+
+pub fn verify(expected: &Expected, input: &str) -> Report {
+    let start = Instant::now();
+    let outcome = check(expected, input);
+
+    Report {
+        outcome,
+        duration: start.elapsed(),
+    }
+}
+
+fn check(expected: &Expected, input: &str) -> Outcome {
+    let hash = input_hash(input);
+    if hash != expected.input_hash {
+        return Outcome::Fail(format!("input hash is {:016x}", hash));
+    }
+
+    let registration = match find(expected.day) {
+        Some(registration) => registration,
+        None => return Outcome::Fail(String::from("day is not registered")),
+    };
+
+    let answer = (registration.parse)(input).and_then(|solver| solver.solve(expected.part));
+
+    match answer {
+        Ok(answer) if answer.to_string() == expected.answer => Outcome::Pass,
+        Ok(answer) => Outcome::Mismatch(answer.to_string()),
+        Err(e) => Outcome::Fail(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), 0xcbf29ce484222325);
+        assert_eq!(input_hash("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(input_hash("7 15"), input_hash("15 7"));
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let manifest = "# comment\n\n4 2 00000000000000ff 30\n";
+        let expected = Expected {
+            day: 4,
+            part: Part::Two,
+            input_hash: 255,
+            answer: String::from("30"),
+        };
+        assert_eq!(parse_manifest(manifest).unwrap(), vec![expected]);
+
+        assert!(parse_manifest("4 3 ff 30").is_err());
+        assert!(parse_manifest("4 1 ff").is_err());
+        assert!(parse_manifest("4 1 xyz 30").is_err());
+    }
+
+    #[test]
+    fn test_verify() {
+        let input = include_str!("../../day-4/test.txt");
+        let mut expected = Expected {
+            day: 4,
+            part: Part::One,
+            input_hash: input_hash(input),
+            answer: String::from("13"),
+        };
+        assert_eq!(verify(&expected, input).outcome, Outcome::Pass);
+
+        expected.answer = String::from("14");
+        assert_eq!(
+            verify(&expected, input).outcome,
+            Outcome::Mismatch(String::from("13"))
+        );
+
+        expected.input_hash += 1;
+        assert!(matches!(verify(&expected, input).outcome, Outcome::Fail(_)));
+    }
+}
@@ -0,0 +1,56 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use day_1::vocabulary::Vocabulary;
+use day_1::{Calibrator, Scan};
+
+const RUNS: usize = 15;
+
+fn median(calibrator: &Calibrator, input: &str) -> Duration {
+    let mut times: Vec<Duration> = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(calibrator.sum(black_box(input)));
+            start.elapsed()
+        })
+        .collect();
+
+    times.sort();
+    times[RUNS / 2]
+}
+
+/// Digits only at both ends, the full scan has to read all the noise in between
+fn long_lines(lines: usize, noise: usize) -> String {
+    let line = format!(
+        "one{}7two",
+        "abcdefghijklmnopqrstuvwxyz".repeat(noise / 26 + 1)
+    );
+    vec![line; lines].join("\n")
+}
+
+fn main() {
+    let vocabulary = Vocabulary::part_two();
+    let full = Calibrator::new(&vocabulary).with_scan(Scan::Full);
+    let bidirectional = Calibrator::new(&vocabulary);
+
+    let puzzle = include_str!("../input.txt");
+    let inputs = [
+        ("puzzle input", puzzle.to_owned()),
+        ("100 lines of 1k chars", long_lines(100, 1_000)),
+        ("100 lines of 100k chars", long_lines(100, 100_000)),
+    ];
+
+    for (name, input) in &inputs {
+        assert_eq!(full.sum(input), bidirectional.sum(input));
+
+        let full_time = median(&full, input);
+        let bidirectional_time = median(&bidirectional, input);
+        println!(
+            "{:<24} full {:>10.2?}  bidirectional {:>10.2?}  ({:.1}x)",
+            name,
+            full_time,
+            bidirectional_time,
+            full_time.as_secs_f64() / bidirectional_time.as_secs_f64()
+        );
+    }
+}
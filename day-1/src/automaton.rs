@@ -0,0 +1,416 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+const ROOT: usize = 0;
+
+#[derive(Default, Debug)]
+struct State {
+    children: HashMap<char, usize>,
+    // Longest proper suffix of this state that is also a prefix of some pattern
+    fail: usize,
+    // Closest state along the fail links that ends a pattern, so matches hidden inside
+    // longer ones (e.g. "one" inside "stone") are reported without walking every fail link
+    output: Option<usize>,
+    // Byte length and value of the pattern ending here
+    pattern: Option<(usize, i32)>,
+}
+
+/// States and edges one text went through, fail links are the edges marked `true`
+struct Trace {
+    states: BTreeSet<usize>,
+    edges: BTreeSet<(usize, usize, bool)>,
+}
+
+/// Aho-Corasick automaton, finds every occurrence of every pattern in a single pass
+#[derive(Debug)]
+pub struct Automaton {
+    states: Vec<State>,
+}
+
+/// One occurrence of a pattern, `start..end` is the byte range in the scanned text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: i32,
+}
+
+impl Automaton {
+    pub fn new<'a, I>(patterns: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, i32)>,
+    {
+        let mut automaton = Automaton {
+            states: vec![State::default()],
+        };
+
+        for (pattern, value) in patterns {
+            automaton.insert(pattern, value);
+        }
+        automaton.link();
+
+        automaton
+    }
+
+    fn insert(&mut self, pattern: &str, value: i32) {
+        let mut state = ROOT;
+
+        for c in pattern.chars() {
+            state = match self.states[state].children.get(&c) {
+                Some(&next) => next,
+                None => {
+                    self.states.push(State::default());
+                    let next = self.states.len() - 1;
+                    self.states[state].children.insert(c, next);
+                    next
+                }
+            };
+        }
+
+        // Empty patterns would match everywhere, nothing useful to report
+        if state != ROOT {
+            self.states[state].pattern = Some((pattern.len(), value));
+        }
+    }
+
+    /// Fills in the fail and output links breadth first, every state's links point to shallower states
+    fn link(&mut self) {
+        let mut queue: VecDeque<usize> = self.states[ROOT].children.values().copied().collect();
+
+        while let Some(state) = queue.pop_front() {
+            let children: Vec<(char, usize)> = self.states[state]
+                .children
+                .iter()
+                .map(|(&c, &child)| (c, child))
+                .collect();
+
+            for (c, child) in children {
+                let fail = self.step(self.states[state].fail, c);
+                self.states[child].fail = fail;
+                self.states[child].output = match self.states[fail].pattern {
+                    Some(_) => Some(fail),
+                    None => self.states[fail].output,
+                };
+
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// Follows fail links until `c` can be consumed, the root takes anything
+    fn step(&self, mut state: usize, c: char) -> usize {
+        loop {
+            if let Some(&next) = self.states[state].children.get(&c) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.states[state].fail;
+        }
+    }
+
+    /// Value of `pattern` if it was added to the automaton
+    pub fn get(&self, pattern: &str) -> Option<i32> {
+        let mut state = ROOT;
+
+        for c in pattern.chars() {
+            state = *self.states[state].children.get(&c)?;
+        }
+        self.states[state].pattern.map(|(_, value)| value)
+    }
+
+    /// Every state as `(state, text it stands for)`, children in alphabetical order
+    fn prefixes(&self) -> Vec<(usize, String)> {
+        let mut prefixes = Vec::new();
+        let mut stack = vec![(ROOT, String::new())];
+
+        while let Some((state, prefix)) = stack.pop() {
+            let mut children: Vec<(&char, &usize)> = self.states[state].children.iter().collect();
+            children.sort();
+            for (c, &child) in children.into_iter().rev() {
+                stack.push((child, format!("{}{}", prefix, c)));
+            }
+            prefixes.push((state, prefix));
+        }
+
+        prefixes
+    }
+
+    fn trace(&self, text: &str) -> Trace {
+        let mut state = ROOT;
+        let mut trace = Trace {
+            states: BTreeSet::from([ROOT]),
+            edges: BTreeSet::new(),
+        };
+
+        for c in text.chars() {
+            loop {
+                if let Some(&next) = self.states[state].children.get(&c) {
+                    trace.edges.insert((state, next, false));
+                    state = next;
+                    break;
+                }
+                if state == ROOT {
+                    break;
+                }
+
+                let fail = self.states[state].fail;
+                trace.edges.insert((state, fail, true));
+                state = fail;
+            }
+            trace.states.insert(state);
+        }
+
+        trace
+    }
+
+    /// Graphviz DOT graph of the automaton, fail links dashed, states ending a pattern doubly circled
+    pub fn to_dot(&self) -> String {
+        self.dot(None)
+    }
+
+    /// `to_dot` with the states and edges `text` goes through in red, fail links it follows included
+    pub fn trace_dot(&self, text: &str) -> String {
+        self.dot(Some(&self.trace(text)))
+    }
+
+    fn dot(&self, trace: Option<&Trace>) -> String {
+        let highlight = " color=red fontcolor=red penwidth=2";
+        let mut lines = vec![
+            String::from("digraph automaton {"),
+            String::from("  rankdir=LR;"),
+        ];
+
+        let prefixes = self.prefixes();
+        for (state, prefix) in &prefixes {
+            let mut attributes = match self.states[*state].pattern {
+                Some((_, value)) => format!(
+                    "label=\"{}\\n= {}\" shape=doublecircle",
+                    escape(prefix),
+                    value
+                ),
+                None => format!("label=\"{}\" shape=circle", escape(prefix)),
+            };
+            if trace.is_some_and(|trace| trace.states.contains(state)) {
+                attributes.push_str(highlight);
+            }
+            lines.push(format!("  {} [{}];", state, attributes));
+        }
+
+        for (state, _) in &prefixes {
+            let mut children: Vec<(&char, &usize)> = self.states[*state].children.iter().collect();
+            children.sort();
+
+            for (c, &child) in children {
+                let mut attributes = format!("label=\"{}\"", escape(&c.to_string()));
+                if trace.is_some_and(|trace| trace.edges.contains(&(*state, child, false))) {
+                    attributes.push_str(highlight);
+                }
+                lines.push(format!("  {} -> {} [{}];", state, child, attributes));
+            }
+        }
+
+        // Links back to the root are the default and would only clutter the graph,
+        // unless the traced text actually took one
+        for (state, _) in &prefixes {
+            let fail = self.states[*state].fail;
+            let traced = trace.is_some_and(|trace| trace.edges.contains(&(*state, fail, true)));
+            if *state == ROOT || (fail == ROOT && !traced) {
+                continue;
+            }
+
+            let mut attributes = String::from("style=dashed");
+            if traced {
+                attributes.push_str(highlight);
+            }
+            lines.push(format!("  {} -> {} [{}];", state, fail, attributes));
+        }
+
+        lines.push(String::from("}"));
+        lines.join("\n")
+    }
+
+    /// Every match in `text`, overlapping ones included, ordered by where they end
+    pub fn find_overlapping<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> Matches<'a, impl Iterator<Item = (usize, char)> + 'a> {
+        self.scan(text.char_indices())
+    }
+
+    /// Same as `find_overlapping` for text given as `(byte offset, char)` pairs in scanning order,
+    /// e.g. a line read back to front
+    pub fn scan<I>(&self, chars: I) -> Matches<'_, I>
+    where
+        I: Iterator<Item = (usize, char)>,
+    {
+        Matches {
+            automaton: self,
+            chars,
+            state: ROOT,
+            end: 0,
+            limit: usize::MAX,
+            pending: None,
+        }
+    }
+}
+
+/// Quotes and backslashes would end a DOT label early
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub struct Matches<'a, I> {
+    automaton: &'a Automaton,
+    chars: I,
+    state: usize,
+    end: usize,
+    limit: usize,
+    // Next state on the output chain of the current position that still has to be reported
+    pending: Option<usize>,
+}
+
+impl<I> Matches<'_, I> {
+    /// Stops scanning before the first char that ends past `limit`, for callers that know nothing
+    /// interesting can come after it
+    pub fn stop_after(&mut self, limit: usize) {
+        self.limit = limit;
+    }
+}
+
+impl<I> Iterator for Matches<'_, I>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    type Item = Match;
+
+    fn next(&mut self) -> Option<Match> {
+        loop {
+            if let Some(state) = self.pending {
+                let state = &self.automaton.states[state];
+                self.pending = state.output;
+
+                if let Some((length, value)) = state.pattern {
+                    return Some(Match {
+                        start: self.end - length,
+                        end: self.end,
+                        value,
+                    });
+                }
+                continue;
+            }
+
+            let (i, c) = self.chars.next()?;
+            if i + c.len_utf8() > self.limit {
+                return None;
+            }
+            self.state = self.automaton.step(self.state, c);
+            self.end = i + c.len_utf8();
+
+            let state = &self.automaton.states[self.state];
+            self.pending = match state.pattern {
+                Some(_) => Some(self.state),
+                None => state.output,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(automaton: &Automaton, text: &str) -> Vec<(usize, usize, i32)> {
+        automaton
+            .find_overlapping(text)
+            .map(|m| (m.start, m.end, m.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let automaton = Automaton::new([("he", 1), ("she", 2), ("his", 3), ("hers", 4)]);
+
+        assert_eq!(
+            matches(&automaton, "ushers"),
+            vec![(1, 4, 2), (2, 4, 1), (2, 6, 4)]
+        );
+        assert_eq!(
+            matches(&automaton, "ahishe"),
+            vec![(1, 4, 3), (3, 6, 2), (4, 6, 1)]
+        );
+        assert!(matches(&automaton, "xyz").is_empty());
+    }
+
+    #[test]
+    fn test_digit_words() {
+        let automaton = Automaton::new([("one", 1), ("two", 2), ("three", 3), ("eight", 8)]);
+
+        assert_eq!(matches(&automaton, "twone"), vec![(0, 3, 2), (2, 5, 1)]);
+        assert_eq!(matches(&automaton, "threight"), vec![(3, 8, 8)]);
+        assert_eq!(matches(&automaton, "threeight"), vec![(0, 5, 3), (4, 9, 8)]);
+        assert_eq!(matches(&automaton, "oneight"), vec![(0, 3, 1), (2, 7, 8)]);
+        assert_eq!(matches(&automaton, "ttwoo"), vec![(1, 4, 2)]);
+    }
+
+    #[test]
+    fn test_stop_after() {
+        let automaton = Automaton::new([("one", 1), ("two", 2)]);
+
+        let mut matches = automaton.find_overlapping("one two one");
+        matches.stop_after(7);
+        assert_eq!(matches.map(|m| m.value).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_byte_offsets() {
+        let automaton = Automaton::new([("one", 1)]);
+        assert_eq!(matches(&automaton, "é one"), vec![(3, 6, 1)]);
+    }
+
+    #[test]
+    fn test_dot() {
+        let automaton = Automaton::new([("ab", 1), ("b", 2)]);
+        let expected = "\
+digraph automaton {
+  rankdir=LR;
+  0 [label=\"\" shape=circle];
+  1 [label=\"a\" shape=circle];
+  2 [label=\"ab\\n= 1\" shape=doublecircle];
+  3 [label=\"b\\n= 2\" shape=doublecircle];
+  0 -> 1 [label=\"a\"];
+  0 -> 3 [label=\"b\"];
+  1 -> 2 [label=\"b\"];
+  2 -> 3 [style=dashed];
+}";
+        assert_eq!(automaton.to_dot(), expected);
+    }
+
+    #[test]
+    fn test_trace_dot() {
+        let automaton = Automaton::new([("ab", 1), ("b", 2)]);
+        let dot = automaton.trace_dot("abb");
+
+        // a, b, then b again after falling back from "ab" to "b" and on to the root
+        assert!(dot.contains("  2 [label=\"ab\\n= 1\" shape=doublecircle color=red"));
+        assert!(dot.contains("  1 -> 2 [label=\"b\" color=red"));
+        assert!(dot.contains("  2 -> 3 [style=dashed color=red"));
+        assert!(dot.contains("  3 -> 0 [style=dashed color=red"));
+        assert!(dot.contains("  0 -> 3 [label=\"b\" color=red"));
+        assert_eq!(dot.matches("penwidth=2").count(), 9);
+
+        assert!(Automaton::new([("\"", 1)])
+            .to_dot()
+            .contains("label=\"\\\"\\n= 1\""));
+    }
+
+    #[test]
+    fn test_get() {
+        let automaton = Automaton::new([("one", 1), ("", 0)]);
+
+        assert_eq!(automaton.get("one"), Some(1));
+        assert_eq!(automaton.get("on"), None);
+        assert_eq!(automaton.get("ones"), None);
+        assert_eq!(automaton.get(""), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Randomized checks of the scanner against a reference that is too slow to be wrong
+
+use aoc_common::Rng;
+
+use super::*;
+
+const CASES: usize = 5_000;
+const SEED: u64 = 0x2023_0001;
+
+/// Bits of text the generator glues together, digit words cut short included
+const NOISE: [&str; 16] = [
+    "a", "e", "n", "o", "t", "x", "z", "on", "tw", "thre", "seve", "eigh", "nin", "fiv", "é", " ",
+];
+
+fn generate(rng: &mut Rng, words: &[(String, i32)]) -> String {
+    let pieces = rng.below(10);
+
+    (0..pieces)
+        .map(|_| match rng.below(3) {
+            0 => words[rng.below(words.len())].0.clone(),
+            1 => rng.below(10).to_string(),
+            _ => NOISE[rng.below(NOISE.len())].to_owned(),
+        })
+        .collect()
+}
+
+/// Words inside words only happen with roman numerals, the longer one wins
+fn longest<'a>(found: impl Iterator<Item = &'a (String, i32)>) -> Option<i32> {
+    found
+        .max_by_key(|(word, _)| word.len())
+        .map(|(_, value)| *value)
+}
+
+/// Tries every word at every offset, starting at it from the front and ending at it from the back
+fn reference(words: &[(String, i32)], line: &str) -> i32 {
+    let starting_at = |i: usize| {
+        longest(
+            words
+                .iter()
+                .filter(|(word, _)| line[i..].starts_with(word.as_str())),
+        )
+    };
+    let ending_at = |i: usize| {
+        longest(
+            words
+                .iter()
+                .filter(|(word, _)| line[..i].ends_with(word.as_str())),
+        )
+    };
+
+    let offsets: Vec<usize> = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain([line.len()])
+        .collect();
+    let first = offsets.iter().find_map(|&i| starting_at(i));
+    let last = offsets.iter().rev().find_map(|&i| ending_at(i));
+
+    match (first, last) {
+        (Some(first), Some(last)) => first * 10 + last,
+        _ => 0,
+    }
+}
+
+/// Drops chunks of chars, halving the chunk size, for as long as `fails` still holds
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut size = chars.len() / 2;
+
+    while size > 0 {
+        let mut start = 0;
+        let mut shrunk = false;
+
+        while start < chars.len() {
+            let end = (start + size).min(chars.len());
+            let candidate: String = chars[..start].iter().chain(&chars[end..]).collect();
+
+            if fails(&candidate) {
+                chars = candidate.chars().collect();
+                shrunk = true;
+            } else {
+                start += size;
+            }
+        }
+
+        if !shrunk {
+            size /= 2;
+        }
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Panics with the smallest input `check` still disagrees on
+fn differential(vocabulary: &Vocabulary, check: impl Fn(&str) -> i32) {
+    let words: Vec<(String, i32)> = vocabulary.words().collect();
+    let fails = |line: &str| check(line) != reference(&words, line);
+    let mut rng = Rng::new(SEED);
+
+    for case in 0..CASES {
+        let line = generate(&mut rng, &words);
+
+        if fails(&line) {
+            let minimal = shrink(&line, fails);
+            panic!(
+                "case {} {:?} shrunk to {:?}: got {}, reference says {}",
+                case,
+                line,
+                minimal,
+                check(&minimal),
+                reference(&words, &minimal)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_part_two() {
+    differential(&Vocabulary::part_two(), |line| {
+        get_calibration_value_part_two(line).unwrap()
+    });
+}
+
+#[test]
+fn test_full_scan() {
+    let calibrator = Calibrator::new(&Vocabulary::part_two()).with_scan(Scan::Full);
+    differential(&Vocabulary::part_two(), |line| calibrator.value(line));
+}
+
+#[test]
+fn test_words_inside_words() {
+    let vocabulary = Vocabulary::roman().with(&Vocabulary::german());
+    let calibrator = Calibrator::new(&vocabulary);
+    differential(&vocabulary, |line| calibrator.value(line));
+}
+
+#[test]
+fn test_reference() {
+    let words: Vec<(String, i32)> = Vocabulary::part_two().words().collect();
+
+    assert_eq!(reference(&words, "twone"), 21);
+    assert_eq!(reference(&words, "xtwonex"), 21);
+    assert_eq!(reference(&words, "7pqrstsixteen"), 76);
+    assert_eq!(reference(&words, "abc"), 0);
+
+    let roman: Vec<(String, i32)> = Vocabulary::roman().words().collect();
+    assert_eq!(reference(&roman, "xVIIIx"), 88);
+}
+
+#[test]
+fn test_shrink() {
+    // A made up bug that only shows when "one" follows a "2" somewhere
+    let fails = |line: &str| {
+        line.find('2')
+            .is_some_and(|digit| line[digit..].contains("one"))
+    };
+
+    assert_eq!(shrink("ab2cdtwoneef", fails), "2one");
+    assert_eq!(shrink("nothing", |_| true), "");
+}
@@ -0,0 +1,517 @@
+use std::sync::OnceLock;
+
+use aoc_common::{read_input, Answer, ParseError, ParseResult, Part, Result, Solver};
+
+pub mod automaton;
+#[cfg(test)]
+mod differential;
+pub mod normalize;
+pub mod phrases;
+pub mod report;
+pub mod vocabulary;
+
+use automaton::{Automaton, Match, Matches};
+use normalize::Normalization;
+use vocabulary::Vocabulary;
+
+pub struct Solution {
+    input: String,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(solve_part_one_str(&self.input)?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(solve_part_two_str(&self.input)?.into())
+    }
+
+    fn warnings(&self, part: Part) -> Vec<String> {
+        let f = match part {
+            Part::One => get_calibration_value_part_one,
+            Part::Two => get_calibration_value_part_two,
+        };
+
+        self.input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| matches!(f(line), Ok(0)))
+            .map(|(i, _)| format!("line {} has no digits, counted as 0", i + 1))
+            .collect()
+    }
+}
+
+fn solve(f: fn(&str) -> Result<i32>, input: &str) -> Result<i32> {
+    let mut answer = 0;
+    for input_string in input.lines() {
+        let calibration_number = f(input_string)?;
+        answer += calibration_number;
+    }
+
+    Ok(answer)
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<i32> {
+    solve(get_calibration_value_part_one, input)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<i32> {
+    solve(get_calibration_value_part_two, input)
+}
+
+pub fn solve_part_one(filename: &str) -> Result<i32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two(filename: &str) -> Result<i32> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+fn get_calibration_value_part_one(line: &str) -> Result<i32> {
+    let digits: Vec<char> = line.chars().filter(|c| c.is_ascii_digit()).collect();
+
+    match (digits.first(), digits.last()) {
+        (Some(first_digit), Some(last_digit)) => {
+            Ok(format!("{}{}", first_digit, last_digit).parse::<i32>()?)
+        }
+        _ => Ok(0),
+    }
+}
+
+/// How `Calibrator` looks for the first and last digit
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scan {
+    /// Looks at every match of the whole line
+    Full,
+    /// Reads from the front for the first digit and from the back for the last one,
+    /// both stop as soon as nothing better can come
+    Bidirectional,
+}
+
+/// What happens to lines without a single digit
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Strictness {
+    /// They count as 0, `Calibration::summary` lists them
+    #[default]
+    Lenient,
+    /// The first one is an error, for input that should never have them
+    Strict,
+}
+
+/// Sum of a whole document and the lines that didn't add anything to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    pub sum: i32,
+    pub lines: usize,
+    /// Line numbers without digits, counting from 1
+    pub skipped: Vec<usize>,
+}
+
+impl Calibration {
+    /// `None` when every line had digits
+    pub fn summary(&self) -> Option<String> {
+        if self.skipped.is_empty() {
+            return None;
+        }
+
+        let numbers: Vec<String> = self.skipped.iter().map(|line| line.to_string()).collect();
+        Some(format!(
+            "{} of {} lines had no digits and counted as 0: {}",
+            self.skipped.len(),
+            self.lines,
+            numbers.join(", ")
+        ))
+    }
+}
+
+/// Finds the first and last digit of a line for any vocabulary
+pub struct Calibrator {
+    forward: Automaton,
+    // Every word spelled backwards, for reading lines back to front
+    backward: Automaton,
+    // Byte length of the longest word, bounds how far a scan goes past its first match
+    longest: usize,
+    scan: Scan,
+    normalization: Normalization,
+}
+
+impl Calibrator {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        Calibrator::normalized(vocabulary, Normalization::none())
+    }
+
+    /// Scans lines after `normalization`, matches still point into the original line
+    pub fn normalized(vocabulary: &Vocabulary, normalization: Normalization) -> Self {
+        let words: Vec<(String, i32)> = vocabulary
+            .words()
+            .map(|(word, value)| (normalization.apply(&word), value))
+            .collect();
+        let reversed: Vec<(String, i32)> = words
+            .iter()
+            .map(|(word, value)| (word.chars().rev().collect(), *value))
+            .collect();
+
+        Calibrator {
+            forward: Automaton::new(words.iter().map(|(word, value)| (word.as_str(), *value))),
+            backward: Automaton::new(reversed.iter().map(|(word, value)| (word.as_str(), *value))),
+            longest: words.iter().map(|(word, _)| word.len()).max().unwrap_or(0),
+            scan: Scan::Bidirectional,
+            normalization,
+        }
+    }
+
+    pub fn with_scan(mut self, scan: Scan) -> Self {
+        self.scan = scan;
+        self
+    }
+
+    /// The matches the first and the last digit come from, `None` if the line has no digits
+    pub fn first_and_last(&self, line: &str) -> Option<(Match, Match)> {
+        if self.normalization.is_none() {
+            return self.scan_line(line);
+        }
+
+        let normalized = self.normalization.normalize(line);
+        let (first, last) = self.scan_line(&normalized.text)?;
+        Some((normalized.restore(first), normalized.restore(last)))
+    }
+
+    fn scan_line(&self, line: &str) -> Option<(Match, Match)> {
+        match self.scan {
+            Scan::Full => self.full_scan(line),
+            Scan::Bidirectional => self.bidirectional_scan(line),
+        }
+    }
+
+    fn full_scan(&self, line: &str) -> Option<(Match, Match)> {
+        // Matches come ordered by their end and words can overlap like in "twone",
+        // so the first digit is the match that starts first and the last one is the match that ends last
+        let mut first: Option<Match> = None;
+        let mut last: Option<Match> = None;
+
+        for found in self.forward.find_overlapping(line) {
+            if first.is_none_or(|first| starts_before(&found, &first)) {
+                first = Some(found);
+            }
+            // The longest match ending at a position is reported first
+            if last.is_none_or(|last| found.end > last.end) {
+                last = Some(found);
+            }
+        }
+
+        Some((first?, last?))
+    }
+
+    fn bidirectional_scan(&self, line: &str) -> Option<(Match, Match)> {
+        let first = earliest(self.forward.find_overlapping(line), self.longest)?;
+
+        // Back to front the last digit is the one that starts first, offsets count from the end
+        let length = line.len();
+        let backwards = line
+            .char_indices()
+            .rev()
+            .map(|(i, c)| (length - i - c.len_utf8(), c));
+        let last = earliest(self.backward.scan(backwards), self.longest)?;
+
+        let last = Match {
+            start: length - last.end,
+            end: length - last.start,
+            value: last.value,
+        };
+        Some((first, last))
+    }
+
+    /// The words as a Graphviz DOT graph, see `Automaton::to_dot`
+    pub fn to_dot(&self) -> String {
+        self.forward.to_dot()
+    }
+
+    /// `to_dot` with the path reading `line` takes highlighted
+    pub fn trace_dot(&self, line: &str) -> String {
+        self.forward.trace_dot(&self.normalization.apply(line))
+    }
+
+    /// Every word of the line, overlapping ones included, ordered by where they end
+    pub fn matches(&self, line: &str) -> impl Iterator<Item = Match> {
+        let found: Vec<Match> = if self.normalization.is_none() {
+            self.forward.find_overlapping(line).collect()
+        } else {
+            let normalized = self.normalization.normalize(line);
+            self.forward
+                .find_overlapping(&normalized.text)
+                .map(|found| normalized.restore(found))
+                .collect()
+        };
+
+        found.into_iter()
+    }
+
+    /// First digit times ten plus the last one, 0 if the line has no digits
+    pub fn value(&self, line: &str) -> i32 {
+        match self.first_and_last(line) {
+            Some((first, last)) => first.value * 10 + last.value,
+            None => 0,
+        }
+    }
+
+    pub fn sum(&self, input: &str) -> i32 {
+        input.lines().map(|line| self.value(line)).sum()
+    }
+
+    /// Like `sum`, but keeps track of the lines without digits or rejects them
+    pub fn calibrate(&self, input: &str, strictness: Strictness) -> ParseResult<Calibration> {
+        let mut calibration = Calibration {
+            sum: 0,
+            lines: 0,
+            skipped: Vec::new(),
+        };
+
+        for (i, line) in input.lines().enumerate() {
+            calibration.lines += 1;
+
+            match self.first_and_last(line) {
+                Some((first, last)) => calibration.sum += first.value * 10 + last.value,
+                None if strictness == Strictness::Strict => {
+                    return Err(ParseError::new(line, line, "line has no digits").on_line(i + 1))
+                }
+                None => calibration.skipped.push(i + 1),
+            }
+        }
+
+        Ok(calibration)
+    }
+}
+
+/// Ties only happen with words inside words like roman "VIII", the longer word wins
+fn starts_before(a: &Match, b: &Match) -> bool {
+    a.start < b.start || (a.start == b.start && a.end > b.end)
+}
+
+/// The match that starts first, without reading further than needed
+fn earliest<I>(mut matches: Matches<I>, longest: usize) -> Option<Match>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    let mut best = matches.next()?;
+
+    // A match ending past this can't start before `best`
+    matches.stop_after(best.start + longest);
+    for found in matches {
+        if starts_before(&found, &best) {
+            best = found;
+        }
+    }
+
+    Some(best)
+}
+
+/// The puzzle's part two, spelled out English digits and the digits themselves
+fn english() -> &'static Calibrator {
+    static ENGLISH: OnceLock<Calibrator> = OnceLock::new();
+
+    ENGLISH.get_or_init(|| Calibrator::new(&Vocabulary::part_two()))
+}
+
+fn get_calibration_value_part_two(line: &str) -> Result<i32> {
+    Ok(english().value(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_get_calibration_value_part_one() {
+        assert_eq!(get_calibration_value_part_one("1abc2").unwrap(), 12);
+        assert_eq!(get_calibration_value_part_one("pqr3stu8vwx").unwrap(), 38);
+        assert_eq!(get_calibration_value_part_one("a1b2c3d4e5f").unwrap(), 15);
+        assert_eq!(get_calibration_value_part_one("treb7uchet").unwrap(), 77);
+        assert_eq!(get_calibration_value_part_one("abc").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let solution = Solution::parse("1abc2\nabcone\nxyz").unwrap();
+
+        assert_eq!(solution.warnings(Part::One).len(), 2);
+        assert_eq!(
+            solution.warnings(Part::Two),
+            vec![String::from("line 3 has no digits, counted as 0")]
+        );
+    }
+
+    #[test]
+    fn test_trie() {
+        let trie = &english().forward;
+
+        assert_eq!(trie.get("one"), Some(1));
+        assert_eq!(trie.get("two"), Some(2));
+        assert_eq!(trie.get("three"), Some(3));
+        assert_eq!(trie.get("four"), Some(4));
+        assert_eq!(trie.get("five"), Some(5));
+        assert_eq!(trie.get("six"), Some(6));
+        assert_eq!(trie.get("seven"), Some(7));
+        assert_eq!(trie.get("eight"), Some(8));
+        assert_eq!(trie.get("nine"), Some(9));
+        assert_eq!(trie.get("nines"), None);
+    }
+
+    #[test]
+    fn test_get_calibration_value_part_two() {
+        assert_eq!(get_calibration_value_part_two("2three5three").unwrap(), 23);
+        assert_eq!(get_calibration_value_part_two("abcontwdfdfdf").unwrap(), 0);
+        assert_eq!(get_calibration_value_part_two("one2").unwrap(), 12);
+        assert_eq!(get_calibration_value_part_two("otwo3").unwrap(), 23);
+        assert_eq!(get_calibration_value_part_two("two1nine").unwrap(), 29);
+        assert_eq!(get_calibration_value_part_two("eightwothree").unwrap(), 83);
+        assert_eq!(
+            get_calibration_value_part_two("abcone2threexyz").unwrap(),
+            13
+        );
+        assert_eq!(get_calibration_value_part_two("xtwone3four").unwrap(), 24);
+        assert_eq!(
+            get_calibration_value_part_two("4nineeightseven2").unwrap(),
+            42
+        );
+        assert_eq!(get_calibration_value_part_two("zoneight234").unwrap(), 14);
+        assert_eq!(get_calibration_value_part_two("onetwone").unwrap(), 11);
+        assert_eq!(get_calibration_value_part_two("sevennine").unwrap(), 79);
+        assert_eq!(get_calibration_value_part_two("7pqrstsixteen").unwrap(), 76);
+        assert_eq!(
+            get_calibration_value_part_two("9one9pjtnncsqzhcszp5").unwrap(),
+            95
+        );
+
+        assert_eq!(get_calibration_value_part_two("sevenninenine").unwrap(), 79);
+        assert_eq!(get_calibration_value_part_two("sevenninenine").unwrap(), 79);
+
+        // Found the culprit. Doesn't pass
+        // Fixed with a peekable lookup of the next char and making a step back
+        assert_eq!(get_calibration_value_part_two("threight").unwrap(), 88);
+
+        // Overlaps the old trie walk couldn't see
+        assert_eq!(get_calibration_value_part_two("sevenine").unwrap(), 79);
+        assert_eq!(get_calibration_value_part_two("eightwo").unwrap(), 82);
+        assert_eq!(get_calibration_value_part_two("oneeighthree").unwrap(), 13);
+        assert_eq!(get_calibration_value_part_two("nineight").unwrap(), 98);
+    }
+
+    #[test]
+    fn test_vocabularies() {
+        let calibrator = Calibrator::new(&Vocabulary::german().with(&Vocabulary::numerals()));
+        assert_eq!(calibrator.value("xdreiundzwei"), 32);
+        assert_eq!(calibrator.value("fünf7"), 57);
+
+        let calibrator = Calibrator::new(&Vocabulary::roman());
+        assert_eq!(calibrator.value("VIII"), 88);
+        assert_eq!(calibrator.value("xIXx"), 99);
+        assert_eq!(calibrator.value("VI and III"), 63);
+
+        let calibrator = Calibrator::new(&Vocabulary::spanish().with(&Vocabulary::french()));
+        assert_eq!(calibrator.value("unodosdeux"), 12);
+
+        let calibrator = Calibrator::new(&Vocabulary::new().word("een", 1).word("twee", 2));
+        assert_eq!(calibrator.sum("een\ntweeeen\nniets"), 11 + 21);
+    }
+
+    #[test]
+    fn test_scans_agree() {
+        let vocabulary = Vocabulary::english()
+            .with(&Vocabulary::numerals())
+            .with(&Vocabulary::roman());
+        let full = Calibrator::new(&vocabulary).with_scan(Scan::Full);
+        let bidirectional = Calibrator::new(&vocabulary);
+
+        for line in [
+            "twone",
+            "eightwothree",
+            "xVIIIx",
+            "VIII",
+            "IX and IV",
+            "é one ü twoé",
+            "nothing here",
+            "",
+            "sevenine",
+        ] {
+            assert_eq!(
+                full.first_and_last(line),
+                bidirectional.first_and_last(line),
+                "{}",
+                line
+            );
+        }
+
+        let (first, last) = bidirectional.first_and_last("xtwone3four").unwrap();
+        assert_eq!((first.start, first.end, first.value), (1, 4, 2));
+        assert_eq!((last.start, last.end, last.value), (7, 11, 4));
+    }
+
+    #[test]
+    fn test_normalization() {
+        let line = "xONE７fünfNEUNx";
+        let vocabulary = Vocabulary::part_two().with(&Vocabulary::german());
+
+        // Only "fünf" is spelled like the vocabulary does
+        assert_eq!(Calibrator::new(&vocabulary).value(line), 55);
+        let case = Normalization::from_spec("case").unwrap();
+        assert_eq!(Calibrator::normalized(&vocabulary, case).value(line), 19);
+        let digits = Normalization::from_spec("digits").unwrap();
+        assert_eq!(Calibrator::normalized(&vocabulary, digits).value(line), 75);
+        let diacritics = Normalization::from_spec("diacritics").unwrap();
+        assert_eq!(
+            Calibrator::normalized(&vocabulary, diacritics).value("funf or fúnf"),
+            55
+        );
+
+        // Ranges point into the line as it was
+        let calibrator = Calibrator::normalized(&vocabulary, Normalization::all());
+        let (first, last) = calibrator.first_and_last(line).unwrap();
+        assert_eq!(&line[first.start..first.end], "ONE");
+        assert_eq!(&line[last.start..last.end], "NEUN");
+        let found: Vec<&str> = calibrator
+            .matches(line)
+            .map(|found| &line[found.start..found.end])
+            .collect();
+        assert_eq!(found, vec!["ONE", "７", "fünf", "NEUN"]);
+    }
+
+    #[test]
+    fn test_dot() {
+        let dot = english().to_dot();
+        assert!(dot.contains("[label=\"eight\\n= 8\" shape=doublecircle]"));
+
+        // "twone" falls back from "tw" + "o" to "o" to finish "one"
+        let dot = english().trace_dot("twone");
+        assert!(dot.contains("[label=\"two\\n= 2\" shape=doublecircle color=red"));
+        assert!(dot.contains("[label=\"one\\n= 1\" shape=doublecircle color=red"));
+        assert!(!dot.contains("[label=\"three\\n= 3\" shape=doublecircle color=red"));
+    }
+
+    #[test]
+    fn test_strictness() {
+        let input = "two1nine\nabc\nxtwone3four\n\n";
+
+        let calibration = english().calibrate(input, Strictness::Lenient).unwrap();
+        assert_eq!(calibration.sum, 53);
+        assert_eq!(calibration.skipped, vec![2, 4]);
+        assert_eq!(
+            calibration.summary().unwrap(),
+            "2 of 4 lines had no digits and counted as 0: 2, 4"
+        );
+
+        let error = english().calibrate(input, Strictness::Strict).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.token, "abc");
+
+        let calibration = english()
+            .calibrate("eightwothree\nzoneight234", Strictness::Strict)
+            .unwrap();
+        assert_eq!((calibration.sum, calibration.summary()), (97, None));
+    }
+}
@@ -0,0 +1,248 @@
+use crate::automaton::Match;
+
+/// First digit of every block of Unicode decimal digits, each block runs from 0 to 9
+const DIGIT_ZEROS: [u32; 37] = [
+    0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66,
+    0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0,
+];
+// The mathematical digits are five blocks in a row: bold, double struck, sans serif, ...
+const MATH_DIGITS: std::ops::RangeInclusive<u32> = 0x1D7CE..=0x1D7FF;
+
+/// Accented Latin letters and the letter they are written on
+const ACCENTED: [(&str, char); 28] = [
+    ("ÀÁÂÃÄÅĀĂĄ", 'A'),
+    ("àáâãäåāăą", 'a'),
+    ("ÇĆĈĊČ", 'C'),
+    ("çćĉċč", 'c'),
+    ("ĎĐ", 'D'),
+    ("ďđ", 'd'),
+    ("ÈÉÊËĒĔĖĘĚ", 'E'),
+    ("èéêëēĕėęě", 'e'),
+    ("ĜĞĠĢ", 'G'),
+    ("ĝğġģ", 'g'),
+    ("ÌÍÎÏĨĪĬĮİ", 'I'),
+    ("ìíîïĩīĭįı", 'i'),
+    ("ĹĻĽĿŁ", 'L'),
+    ("ĺļľŀł", 'l'),
+    ("ÑŃŅŇ", 'N'),
+    ("ñńņň", 'n'),
+    ("ÒÓÔÕÖØŌŎŐ", 'O'),
+    ("òóôõöøōŏő", 'o'),
+    ("ŔŖŘ", 'R'),
+    ("ŕŗř", 'r'),
+    ("ŚŜŞŠ", 'S'),
+    ("śŝşš", 's'),
+    ("ŢŤŦ", 'T'),
+    ("ţťŧ", 't'),
+    ("ÙÚÛÜŨŪŬŮŰŲ", 'U'),
+    ("ùúûüũūŭůűų", 'u'),
+    ("ÝŸŶ", 'Y'),
+    ("ýÿŷ", 'y'),
+];
+/// Combining marks, what accents turn into when text is stored decomposed
+const COMBINING: std::ops::RangeInclusive<char> = '\u{0300}'..='\u{036F}';
+
+/// Opt-in ways of making different spellings of the same digit look alike,
+/// applied to the words of the vocabulary as well as to the scanned text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Normalization {
+    /// "One" and "NINE" are "one" and "nine"
+    pub fold_case: bool,
+    /// Any decimal digit like the full-width "７" is its ASCII digit
+    pub unicode_digits: bool,
+    /// "fünf" and "fúnf" are "funf"
+    pub ignore_diacritics: bool,
+}
+
+/// Text after normalization and where each of its bytes came from
+pub struct Normalized {
+    pub text: String,
+    // Byte range in the original text of the char that produced each byte
+    origins: Vec<(usize, usize)>,
+}
+
+impl Normalized {
+    /// Byte range in the original text covering `start..end` of the normalized one
+    pub fn origin(&self, start: usize, end: usize) -> (usize, usize) {
+        (self.origins[start].0, self.origins[end - 1].1)
+    }
+
+    /// A match found in the normalized text, moved onto the original one
+    pub fn restore(&self, found: Match) -> Match {
+        let (start, end) = self.origin(found.start, found.end);
+        Match {
+            start,
+            end,
+            value: found.value,
+        }
+    }
+}
+
+/// ASCII digit for any Unicode decimal digit
+pub fn decimal_digit(c: char) -> Option<char> {
+    let code = c as u32;
+    let offset = if MATH_DIGITS.contains(&code) {
+        (code - MATH_DIGITS.start()) % 10
+    } else {
+        let zero = DIGIT_ZEROS
+            .iter()
+            .find(|&&zero| (zero..zero + 10).contains(&code))?;
+        code - zero
+    };
+
+    char::from_digit(offset, 10)
+}
+
+impl Normalization {
+    pub fn none() -> Self {
+        Normalization::default()
+    }
+
+    pub fn all() -> Self {
+        Normalization {
+            fold_case: true,
+            unicode_digits: true,
+            ignore_diacritics: true,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == Normalization::none()
+    }
+
+    /// Comma separated modes, `case`, `digits`, `diacritics` or `all`
+    pub fn from_spec(spec: &str) -> Option<Self> {
+        let mut normalization = Normalization::none();
+
+        for name in spec.split(',').map(str::trim) {
+            match name {
+                "case" => normalization.fold_case = true,
+                "digits" => normalization.unicode_digits = true,
+                "diacritics" => normalization.ignore_diacritics = true,
+                "all" => normalization = Normalization::all(),
+                _ => return None,
+            }
+        }
+
+        Some(normalization)
+    }
+
+    /// What `c` turns into, nothing for combining marks when diacritics are ignored
+    fn normalize_char(&self, c: char, into: &mut String) {
+        if self.ignore_diacritics && COMBINING.contains(&c) {
+            return;
+        }
+
+        let mut c = c;
+        if self.unicode_digits {
+            c = decimal_digit(c).unwrap_or(c);
+        }
+        if self.ignore_diacritics {
+            if let Some((_, base)) = ACCENTED.iter().find(|(accented, _)| accented.contains(c)) {
+                c = *base;
+            }
+        }
+
+        if self.fold_case {
+            into.extend(c.to_lowercase());
+        } else {
+            into.push(c);
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        self.normalize(text).text
+    }
+
+    pub fn normalize(&self, text: &str) -> Normalized {
+        let mut normalized = Normalized {
+            text: String::with_capacity(text.len()),
+            origins: Vec::with_capacity(text.len()),
+        };
+
+        for (i, c) in text.char_indices() {
+            let length = normalized.text.len();
+            self.normalize_char(c, &mut normalized.text);
+
+            let origin = (i, i + c.len_utf8());
+            if normalized.text.len() == length {
+                // A dropped mark belongs to the char before it
+                if let Some(last) = normalized.origins.last_mut() {
+                    last.1 = origin.1;
+                }
+            }
+            normalized.origins.resize(normalized.text.len(), origin);
+        }
+
+        normalized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal_digit() {
+        assert_eq!(decimal_digit('７'), Some('7'));
+        assert_eq!(decimal_digit('٣'), Some('3'));
+        assert_eq!(decimal_digit('९'), Some('9'));
+        assert_eq!(decimal_digit('𝟘'), Some('0'));
+        assert_eq!(decimal_digit('𝟿'), Some('9'));
+        assert_eq!(decimal_digit('x'), None);
+        // Numeric, but not a decimal digit
+        assert_eq!(decimal_digit('½'), None);
+
+        for zero in DIGIT_ZEROS {
+            for code in zero..zero + 10 {
+                assert!(char::from_u32(code).unwrap().is_numeric(), "{:x}", code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_modes() {
+        let text = "One ７ fünf NEUN";
+
+        assert_eq!(Normalization::none().apply(text), text);
+        assert_eq!(
+            Normalization::from_spec("case").unwrap().apply(text),
+            "one ７ fünf neun"
+        );
+        assert_eq!(
+            Normalization::from_spec("digits").unwrap().apply(text),
+            "One 7 fünf NEUN"
+        );
+        assert_eq!(
+            Normalization::from_spec("diacritics").unwrap().apply(text),
+            "One ７ funf NEUN"
+        );
+        assert_eq!(Normalization::all().apply(text), "one 7 funf neun");
+        // Decomposed, an "u" followed by a combining diaeresis
+        assert_eq!(Normalization::all().apply("fu\u{308}nf"), "funf");
+
+        assert_eq!(
+            Normalization::from_spec("case, digits").unwrap(),
+            Normalization {
+                fold_case: true,
+                unicode_digits: true,
+                ignore_diacritics: false,
+            }
+        );
+        assert!(Normalization::from_spec("case,klingon").is_none());
+    }
+
+    #[test]
+    fn test_origin() {
+        let normalized = Normalization::all().normalize("é７Xu\u{308}");
+
+        assert_eq!(normalized.text, "e7xu");
+        assert_eq!(normalized.origin(0, 1), (0, 2));
+        assert_eq!(normalized.origin(1, 2), (2, 5));
+        // The dropped mark goes with the "u"
+        assert_eq!(normalized.origin(3, 4), (6, 9));
+        assert_eq!(normalized.origin(0, 4), (0, 9));
+    }
+}
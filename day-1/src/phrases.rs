@@ -0,0 +1,245 @@
+use std::sync::OnceLock;
+
+use aoc_common::Trie;
+
+/// Number words by the role they play in a phrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Word {
+    Zero,
+    Unit(u64),
+    Teen(u64),
+    Tens(u64),
+    Hundred,
+    Scale(u64),
+    And,
+}
+
+const UNITS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const TEENS: [&str; 10] = [
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+const SCALES: [(&str, u64); 4] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+    ("trillion", 1_000_000_000_000),
+];
+
+/// A number spelled out in the text, `start..end` is its byte range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub start: usize,
+    pub end: usize,
+    pub value: u64,
+}
+
+fn words() -> &'static Trie<char, Word> {
+    static WORDS: OnceLock<Trie<char, Word>> = OnceLock::new();
+
+    WORDS.get_or_init(|| {
+        let mut trie = Trie::new();
+        trie.insert("zero".chars(), Word::Zero);
+        trie.insert("hundred".chars(), Word::Hundred);
+        trie.insert("and".chars(), Word::And);
+
+        for (word, value) in UNITS.iter().zip(1..) {
+            trie.insert(word.chars(), Word::Unit(value));
+        }
+        for (word, value) in TEENS.iter().zip(10..) {
+            trie.insert(word.chars(), Word::Teen(value));
+        }
+        for (word, value) in TENS.iter().zip(2..) {
+            trie.insert(word.chars(), Word::Tens(value * 10));
+        }
+        for (word, value) in SCALES {
+            trie.insert(word.chars(), Word::Scale(value));
+        }
+        trie
+    })
+}
+
+/// Longest number word at the start of `text` and its byte length, every word is ASCII
+fn word_at(text: &str) -> Option<(Word, usize)> {
+    words()
+        .longest_prefix(text.chars())
+        .map(|(length, word)| (*word, length))
+}
+
+/// Reads one phrase word by word, stops at the first word that can't follow the previous ones
+#[derive(Default)]
+struct Phrase {
+    total: u64,
+    // The part below the last scale word, e.g. 105 in "two thousand one hundred and five"
+    group: u64,
+    previous: Option<Word>,
+    smallest_scale: Option<u64>,
+}
+
+impl Phrase {
+    fn accepts(&self, word: Word) -> bool {
+        let after_round = matches!(
+            self.previous,
+            None | Some(Word::Hundred | Word::Scale(_) | Word::And)
+        );
+
+        match word {
+            Word::Zero => self.previous.is_none(),
+            Word::Unit(_) => after_round || matches!(self.previous, Some(Word::Tens(_))),
+            Word::Teen(_) | Word::Tens(_) => after_round,
+            Word::Hundred => matches!(self.previous, Some(Word::Unit(_))) && self.group < 10,
+            Word::Scale(scale) => {
+                self.group > 0
+                    && !matches!(self.previous, Some(Word::And))
+                    && self.smallest_scale.is_none_or(|smallest| scale < smallest)
+            }
+            // Only joins words, "and" at the end isn't part of the number
+            Word::And => matches!(self.previous, Some(Word::Hundred | Word::Scale(_))),
+        }
+    }
+
+    fn push(&mut self, word: Word) {
+        match word {
+            Word::Zero | Word::And => {}
+            Word::Unit(value) | Word::Teen(value) | Word::Tens(value) => self.group += value,
+            Word::Hundred => self.group *= 100,
+            Word::Scale(scale) => {
+                self.total += self.group * scale;
+                self.group = 0;
+                self.smallest_scale = Some(scale);
+            }
+        }
+        self.previous = Some(word);
+    }
+
+    fn value(&self) -> u64 {
+        self.total + self.group
+    }
+}
+
+/// Reads the phrase starting at `start`, `None` if no number starts there
+fn phrase_at(text: &str, start: usize) -> Option<Number> {
+    let mut phrase = Phrase::default();
+    let mut end = start;
+    // Where the number ends if nothing more gets added, "and" is only kept once something follows it
+    let mut number_end = start;
+    let mut i = start;
+
+    while let Some((word, length)) = word_at(&text[i..]).filter(|(word, _)| phrase.accepts(*word)) {
+        phrase.push(word);
+        end = i + length;
+        if word != Word::And {
+            number_end = end;
+        }
+
+        // Words can be glued together or split by a single space or hyphen
+        i = match text[end..].chars().next() {
+            Some(' ' | '-') => end + 1,
+            _ => end,
+        };
+        if matches!(word, Word::Zero) {
+            break;
+        }
+    }
+
+    (end > start).then(|| Number {
+        start,
+        end: number_end,
+        value: phrase.value(),
+    })
+}
+
+/// Every spelled out number in `text` from left to right, e.g. 23 and 105 in
+/// "twenty-three then one hundred and five", phrases don't overlap
+pub fn numbers(text: &str) -> Vec<Number> {
+    let mut found = Vec::new();
+    let mut i = 0;
+
+    while i < text.len() {
+        match phrase_at(text, i) {
+            Some(number) => {
+                i = number.end;
+                found.push(number);
+            }
+            None => i += text[i..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(text: &str) -> Vec<u64> {
+        numbers(text).iter().map(|number| number.value).collect()
+    }
+
+    #[test]
+    fn test_phrases() {
+        assert_eq!(values("twenty-three"), vec![23]);
+        assert_eq!(values("one hundred and five"), vec![105]);
+        assert_eq!(values("seventeen"), vec![17]);
+        assert_eq!(values("zero"), vec![0]);
+        assert_eq!(
+            values("two million three hundred thousand and forty-two"),
+            vec![2_300_042]
+        );
+        assert_eq!(
+            values("nine hundred ninety-nine trillion nine hundred ninety-nine billion"),
+            vec![999_999_000_000_000]
+        );
+    }
+
+    #[test]
+    fn test_embedded() {
+        assert_eq!(
+            values("xtwentythreex, then one hundred and five!"),
+            vec![23, 105]
+        );
+        // Puzzle lines glue single digits together, they stay separate numbers
+        assert_eq!(values("eightwothree"), vec![8, 3]);
+        assert_eq!(values("fivesix"), vec![5, 6]);
+        assert_eq!(values("twenty seventeen"), vec![20, 17]);
+        assert_eq!(values("one thousand one thousand"), vec![1001]);
+        assert_eq!(values("one thousand, one thousand"), vec![1000, 1000]);
+        assert_eq!(values("no numbers here"), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let text = "a hundred and one and two hundred and";
+
+        // "a hundred" doesn't start with a number word, the trailing "and" is dropped
+        assert_eq!(
+            numbers(text),
+            vec![
+                Number {
+                    start: 14,
+                    end: 17,
+                    value: 1
+                },
+                Number {
+                    start: 22,
+                    end: 33,
+                    value: 200
+                },
+            ]
+        );
+        assert_eq!(&text[22..33], "two hundred");
+    }
+}
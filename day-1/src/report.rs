@@ -0,0 +1,237 @@
+use aoc_common::json_string;
+
+use crate::Calibrator;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Digit,
+    Word,
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Digit => "digit",
+            Kind::Word => "word",
+        }
+    }
+}
+
+/// One matched word of a line, `offset` is in bytes from the start of the line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub offset: usize,
+    pub text: String,
+    pub kind: Kind,
+    pub value: i32,
+}
+
+/// Everything the calibrator saw on one line, for finding out why a total is off
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineReport {
+    pub line: usize,
+    pub text: String,
+    pub tokens: Vec<Token>,
+    pub first: Option<i32>,
+    pub last: Option<i32>,
+    pub value: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+pub fn report(calibrator: &Calibrator, input: &str) -> Vec<LineReport> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let mut tokens: Vec<Token> = calibrator
+                .matches(line)
+                .map(|found| {
+                    let text = &line[found.start..found.end];
+                    let kind = if text.chars().all(char::is_numeric) {
+                        Kind::Digit
+                    } else {
+                        Kind::Word
+                    };
+
+                    Token {
+                        offset: found.start,
+                        text: text.to_owned(),
+                        kind,
+                        value: found.value,
+                    }
+                })
+                .collect();
+            // Reads better in the order the words start in
+            tokens.sort_by_key(|token| token.offset);
+
+            let first_and_last = calibrator.first_and_last(line);
+            LineReport {
+                line: i + 1,
+                text: line.to_owned(),
+                tokens,
+                first: first_and_last.map(|(first, _)| first.value),
+                last: first_and_last.map(|(_, last)| last.value),
+                value: calibrator.value(line),
+            }
+        })
+        .collect()
+}
+
+pub fn format(reports: &[LineReport], format: Format) -> String {
+    match format {
+        Format::Csv => to_csv(reports),
+        Format::Json => to_json(reports),
+    }
+}
+
+/// Quotes fields only when they need it
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_owned()
+    }
+}
+
+fn optional(value: Option<i32>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Tokens go into a single column as space separated `offset:text:kind`
+pub fn to_csv(reports: &[LineReport]) -> String {
+    let mut csv = String::from("line,text,tokens,first,last,value\n");
+
+    for report in reports {
+        let tokens: Vec<String> = report
+            .tokens
+            .iter()
+            .map(|token| format!("{}:{}:{}", token.offset, token.text, token.kind.name()))
+            .collect();
+
+        csv.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            report.line,
+            csv_field(&report.text),
+            csv_field(&tokens.join(" ")),
+            optional(report.first),
+            optional(report.last),
+            report.value
+        ));
+    }
+
+    csv
+}
+
+fn json_optional(value: Option<i32>) -> String {
+    value
+        .map(|value| value.to_string())
+        .unwrap_or_else(|| String::from("null"))
+}
+
+/// A JSON array with one object per line
+pub fn to_json(reports: &[LineReport]) -> String {
+    let lines: Vec<String> = reports
+        .iter()
+        .map(|report| {
+            let tokens: Vec<String> = report
+                .tokens
+                .iter()
+                .map(|token| {
+                    format!(
+                        "{{\"offset\":{},\"text\":{},\"kind\":\"{}\",\"value\":{}}}",
+                        token.offset,
+                        json_string(&token.text),
+                        token.kind.name(),
+                        token.value
+                    )
+                })
+                .collect();
+
+            format!(
+                "{{\"line\":{},\"text\":{},\"tokens\":[{}],\"first\":{},\"last\":{},\"value\":{}}}",
+                report.line,
+                json_string(&report.text),
+                tokens.join(","),
+                json_optional(report.first),
+                json_optional(report.last),
+                report.value
+            )
+        })
+        .collect();
+
+    format!("[{}]", lines.join(",\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn test_report() {
+        let calibrator = Calibrator::new(&Vocabulary::part_two());
+        let reports = report(&calibrator, "xtwone3\nabc");
+
+        let expected = LineReport {
+            line: 1,
+            text: String::from("xtwone3"),
+            tokens: vec![
+                Token {
+                    offset: 1,
+                    text: String::from("two"),
+                    kind: Kind::Word,
+                    value: 2,
+                },
+                Token {
+                    offset: 3,
+                    text: String::from("one"),
+                    kind: Kind::Word,
+                    value: 1,
+                },
+                Token {
+                    offset: 6,
+                    text: String::from("3"),
+                    kind: Kind::Digit,
+                    value: 3,
+                },
+            ],
+            first: Some(2),
+            last: Some(3),
+            value: 23,
+        };
+        assert_eq!(reports[0], expected);
+        assert_eq!((reports[1].first, reports[1].value), (None, 0));
+    }
+
+    #[test]
+    fn test_csv() {
+        let calibrator = Calibrator::new(&Vocabulary::part_two());
+        let reports = report(&calibrator, "a,one2\nabc");
+
+        let expected = "\
+line,text,tokens,first,last,value
+1,\"a,one2\",2:one:word 5:2:digit,1,2,12
+2,abc,,,,0
+";
+        assert_eq!(to_csv(&reports), expected);
+    }
+
+    #[test]
+    fn test_json() {
+        let calibrator = Calibrator::new(&Vocabulary::part_one());
+        let reports = report(&calibrator, "a\"7\nabc");
+
+        let expected = concat!(
+            "[{\"line\":1,\"text\":\"a\\\"7\",\"tokens\":[",
+            "{\"offset\":2,\"text\":\"7\",\"kind\":\"digit\",\"value\":7}],",
+            "\"first\":7,\"last\":7,\"value\":77},\n",
+            "{\"line\":2,\"text\":\"abc\",\"tokens\":[],\"first\":null,\"last\":null,\"value\":0}]"
+        );
+        assert_eq!(to_json(&reports), expected);
+    }
+}
@@ -0,0 +1,240 @@
+use aoc_common::{parse_token, read_input, ParseError, ParseResult, Result, Trie};
+
+const ENGLISH: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const GERMAN: [&str; 9] = [
+    "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+const FRENCH: [&str; 9] = [
+    "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+];
+const SPANISH: [&str; 9] = [
+    "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+];
+const ROMAN: [&str; 9] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX"];
+const NUMERALS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+/// Names accepted by `Vocabulary::builtin`
+pub const BUILTIN: [&str; 6] = [
+    "english", "german", "french", "spanish", "roman", "numerals",
+];
+
+/// Words the calibration scanner looks for and the digit each one stands for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Vocabulary {
+    words: Trie<char, i32>,
+}
+
+impl Vocabulary {
+    pub fn new() -> Self {
+        Vocabulary::default()
+    }
+
+    /// Builder style, a word that is already there gets the new value
+    pub fn word(mut self, word: &str, value: i32) -> Self {
+        self.words.insert(word.chars(), value);
+        self
+    }
+
+    /// Mixes in every word of `other`, its values win on conflicts
+    pub fn with(mut self, other: &Vocabulary) -> Self {
+        for (word, value) in other.words.iter() {
+            self.words.insert(word, *value);
+        }
+        self
+    }
+
+    fn counting(words: &[&str], first: i32) -> Self {
+        words
+            .iter()
+            .zip(first..)
+            .fold(Vocabulary::new(), |vocabulary, (word, value)| {
+                vocabulary.word(word, value)
+            })
+    }
+
+    pub fn english() -> Self {
+        Vocabulary::counting(&ENGLISH, 1)
+    }
+
+    pub fn german() -> Self {
+        Vocabulary::counting(&GERMAN, 1)
+    }
+
+    pub fn french() -> Self {
+        Vocabulary::counting(&FRENCH, 1)
+    }
+
+    pub fn spanish() -> Self {
+        Vocabulary::counting(&SPANISH, 1)
+    }
+
+    /// Upper case I to IX
+    pub fn roman() -> Self {
+        Vocabulary::counting(&ROMAN, 1)
+    }
+
+    /// The digits themselves, 0 included
+    pub fn numerals() -> Self {
+        Vocabulary::counting(&NUMERALS, 0)
+    }
+
+    /// What the puzzle's part one looks for
+    pub fn part_one() -> Self {
+        Vocabulary::numerals()
+    }
+
+    /// What the puzzle's part two looks for
+    pub fn part_two() -> Self {
+        Vocabulary::english().with(&Vocabulary::numerals())
+    }
+
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "english" => Some(Vocabulary::english()),
+            "german" => Some(Vocabulary::german()),
+            "french" => Some(Vocabulary::french()),
+            "spanish" => Some(Vocabulary::spanish()),
+            "roman" => Some(Vocabulary::roman()),
+            "numerals" => Some(Vocabulary::numerals()),
+            _ => None,
+        }
+    }
+
+    /// One `<word> <value>` pair per line, `#` starts a comment
+    pub fn parse(text: &str) -> ParseResult<Self> {
+        let mut vocabulary = Vocabulary::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = content.split_whitespace();
+            let word = fields.next().unwrap_or(content);
+            let value = match fields.next() {
+                Some(token) => parse_token::<i32>(line, token).map_err(|e| e.on_line(i + 1))?,
+                None => {
+                    return Err(
+                        ParseError::at_end(line, "expected a value after the word").on_line(i + 1)
+                    )
+                }
+            };
+            if let Some(token) = fields.next() {
+                return Err(ParseError::new(line, token, "unexpected field").on_line(i + 1));
+            }
+
+            vocabulary = vocabulary.word(word, value);
+        }
+
+        Ok(vocabulary)
+    }
+
+    pub fn from_file(filename: &str) -> Result<Self> {
+        let text = read_input(filename)?;
+        Vocabulary::parse(&text).map_err(|e| e.in_file(filename).into())
+    }
+
+    /// Comma separated built-in names and vocabulary files, mixed in order, e.g. `english,numerals,extra.txt`
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let mut vocabulary = Vocabulary::new();
+
+        for name in spec
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let other = match Vocabulary::builtin(name) {
+                Some(builtin) => builtin,
+                None => Vocabulary::from_file(name)?,
+            };
+            vocabulary = vocabulary.with(&other);
+        }
+
+        Ok(vocabulary)
+    }
+
+    /// Every word in alphabetical order
+    pub fn words(&self) -> impl Iterator<Item = (String, i32)> + '_ {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.into_iter().collect(), *value))
+    }
+
+    pub fn trie(&self) -> &Trie<char, i32> {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let vocabulary = Vocabulary::new()
+            .word("one", 1)
+            .word("uno", 1)
+            .word("one", 7);
+
+        assert_eq!(
+            vocabulary.words().collect::<Vec<_>>(),
+            vec![(String::from("one"), 7), (String::from("uno"), 1)]
+        );
+        assert_eq!(
+            vocabulary.trie().longest_prefix("ones".chars()),
+            Some((3, &7))
+        );
+    }
+
+    #[test]
+    fn test_mixing() {
+        let vocabulary = Vocabulary::english().with(&Vocabulary::numerals());
+        assert_eq!(vocabulary.len(), 19);
+
+        let vocabulary = Vocabulary::french().with(&Vocabulary::english());
+        // "six" is in both
+        assert_eq!(vocabulary.len(), 17);
+
+        for name in BUILTIN {
+            assert!(!Vocabulary::builtin(name).unwrap().is_empty());
+        }
+        assert!(Vocabulary::builtin("klingon").is_none());
+    }
+
+    #[test]
+    fn test_parse() {
+        let vocabulary = Vocabulary::parse("# Dutch\neen 1\n\n  twee   2\n").unwrap();
+        assert_eq!(
+            vocabulary.words().collect::<Vec<_>>(),
+            vec![(String::from("een"), 1), (String::from("twee"), 2)]
+        );
+
+        let error = Vocabulary::parse("een 1\ntwee x").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 6));
+
+        let error = Vocabulary::parse("een").unwrap_err();
+        assert_eq!(error.message, "expected a value after the word");
+
+        let error = Vocabulary::parse("een 1 2").unwrap_err();
+        assert_eq!(error.column, 7);
+    }
+
+    #[test]
+    fn test_from_spec() {
+        let vocabulary = Vocabulary::from_spec("german, roman").unwrap();
+        assert_eq!(vocabulary, Vocabulary::german().with(&Vocabulary::roman()));
+
+        assert!(Vocabulary::from_spec("english,missing.txt").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use aoc_common::{parse_token, read_input, ParseError, ParseResult, Result};
+
+/// How many cubes of each color the bag holds, colors it doesn't list it has none of
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bag {
+    cubes: BTreeMap<String, u32>,
+}
+
+impl Bag {
+    pub fn new() -> Self {
+        Bag::default()
+    }
+
+    /// Builder style, a color that is already there gets the new count
+    pub fn with(mut self, color: &str, count: u32) -> Self {
+        self.cubes.insert(color.to_owned(), count);
+        self
+    }
+
+    /// What the elf says the bag holds in part one
+    pub fn puzzle() -> Self {
+        Bag::new()
+            .with("red", 12)
+            .with("green", 13)
+            .with("blue", 14)
+    }
+
+    pub fn get(&self, color: &str) -> u32 {
+        self.cubes.get(color).copied().unwrap_or(0)
+    }
+
+    /// Colors and counts, alphabetically
+    pub fn cubes(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.cubes
+            .iter()
+            .map(|(color, count)| (color.as_str(), *count))
+    }
+
+    pub fn len(&self) -> usize {
+        self.cubes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cubes.is_empty()
+    }
+
+    fn add(&mut self, line: &str, color: &str, count: &str) -> ParseResult<()> {
+        let count = parse_token::<u32>(line, count)?;
+        if self.cubes.insert(color.to_owned(), count).is_some() {
+            return Err(ParseError::new(line, color, "color listed twice"));
+        }
+        Ok(())
+    }
+
+    /// `color=count` pairs separated by commas, e.g. `red=12,green=13,blue=14`
+    pub fn parse_spec(spec: &str) -> ParseResult<Self> {
+        let mut bag = Bag::new();
+
+        for pair in spec.split(',').filter(|pair| !pair.trim().is_empty()) {
+            let (color, count) = pair
+                .split_once('=')
+                .ok_or_else(|| ParseError::new(spec, pair, "expected `color=count`").on_line(1))?;
+            bag.add(spec, color.trim(), count.trim())
+                .map_err(|e| e.on_line(1))?;
+        }
+
+        Ok(bag)
+    }
+
+    /// One `<color> <count>` pair per line, `#` starts a comment
+    pub fn parse(text: &str) -> ParseResult<Self> {
+        let mut bag = Bag::new();
+
+        for (i, line) in text.lines().enumerate() {
+            let content = line.trim();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = content.split_whitespace();
+            let color = fields.next().unwrap_or(content);
+            let count = fields.next().ok_or_else(|| {
+                ParseError::at_end(line, "expected a count after the color").on_line(i + 1)
+            })?;
+            if let Some(token) = fields.next() {
+                return Err(ParseError::new(line, token, "unexpected field").on_line(i + 1));
+            }
+
+            bag.add(line, color, count).map_err(|e| e.on_line(i + 1))?;
+        }
+
+        Ok(bag)
+    }
+
+    pub fn from_file(filename: &str) -> Result<Self> {
+        let text = read_input(filename)?;
+        Bag::parse(&text).map_err(|e| e.in_file(filename).into())
+    }
+}
+
+/// Same format `Bag::parse_spec` reads
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairs: Vec<String> = self
+            .cubes()
+            .map(|(color, count)| format!("{}={}", color, count))
+            .collect();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let bag = Bag::puzzle().with("red", 1).with("purple", 3);
+
+        assert_eq!(
+            bag.cubes().collect::<Vec<_>>(),
+            vec![("blue", 14), ("green", 13), ("purple", 3), ("red", 1)]
+        );
+        assert_eq!(bag.get("orange"), 0);
+    }
+
+    #[test]
+    fn test_parse_spec() {
+        assert_eq!(
+            Bag::parse_spec("red=12, green=13,blue=14").unwrap(),
+            Bag::puzzle()
+        );
+        assert!(Bag::parse_spec("").unwrap().is_empty());
+        assert_eq!(
+            Bag::parse_spec(&Bag::puzzle().to_string()).unwrap(),
+            Bag::puzzle()
+        );
+
+        let error = Bag::parse_spec("red=12,green").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (8, "expected `color=count`")
+        );
+
+        let error = Bag::parse_spec("red=12,red=x").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (12, "x"));
+
+        let error = Bag::parse_spec("red=12,red=1").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (8, "color listed twice")
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let bag = Bag::parse("# the usual\nred 12\n\n  green  13\nblue 14\n").unwrap();
+        assert_eq!(bag, Bag::puzzle());
+
+        let error = Bag::parse("red 12\ngreen").unwrap_err();
+        assert_eq!(
+            (error.line, error.message.as_str()),
+            (2, "expected a count after the color")
+        );
+
+        let error = Bag::parse("red 12 13").unwrap_err();
+        assert_eq!(error.column, 8);
+
+        assert!(Bag::from_file("missing.txt").is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::BTreeSet;
+use std::fmt;
+
+use aoc_common::{parse_token, ParseError, ParseResult, Result};
+
+use crate::bag::Bag;
+
+/// Cubes of one color the elf shows, like `3 blue`, any word is a color
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Draw {
+    pub count: u32,
+    pub color: String,
+}
+
+/// One handful of cubes, everything between two `;`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub draws: Vec<Draw>,
+}
+
+impl Round {
+    /// Cubes of `color` shown in this round, 0 if it didn't come up
+    pub fn count(&self, color: &str) -> u32 {
+        self.draws
+            .iter()
+            .find(|draw| draw.color == color)
+            .map_or(0, |draw| draw.count)
+    }
+
+    /// Colors alphabetically, what `fmt` writes, every draw is kept as it was
+    pub fn canonical(&self) -> Round {
+        let mut draws = self.draws.clone();
+        draws.sort_by(|a, b| a.color.cmp(&b.color));
+
+        Round { draws }
+    }
+
+    /// The same draws, maybe in another order
+    pub fn same_draws(&self, other: &Round) -> bool {
+        self.draws.len() == other.draws.len()
+            && self.draws.iter().all(|draw| other.draws.contains(draw))
+    }
+}
+
+/// A whole line, rounds in the order they were played
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub rounds: Vec<Round>,
+}
+
+impl Game {
+    pub fn new(line: &str) -> ParseResult<Self> {
+        let (header, results) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at_end(line, "expected `:` after the game id"))?;
+
+        // Just in case the elf is funny and the input is not sorted by the game id
+        let id = match header.split_whitespace().last() {
+            Some(token) => parse_token::<u32>(line, token)?,
+            None => return Err(ParseError::new(line, header, "expected a game id")),
+        };
+
+        let rounds = results
+            .split(';')
+            .map(|round| Game::parse_round(line, round))
+            .collect::<ParseResult<Vec<Round>>>()?;
+
+        Ok(Game { id, rounds })
+    }
+
+    fn parse_round(line: &str, round: &str) -> ParseResult<Round> {
+        let mut draws = Vec::new();
+
+        for draw in round.split(',') {
+            let mut parts = draw.split_whitespace();
+
+            let count = match parts.next() {
+                Some(token) => parse_token::<u32>(line, token)?,
+                None => return Err(ParseError::new(line, draw, "expected a cube count")),
+            };
+            let color = match parts.next() {
+                Some(token) => token,
+                None => {
+                    let end = &draw[draw.len()..];
+                    return Err(ParseError::new(line, end, "expected a cube color"));
+                }
+            };
+            if let Some(token) = parts.next() {
+                return Err(ParseError::new(line, token, "unexpected field"));
+            }
+            // The elf shows each color once per handful, `1 red, 2 red` is a typo rather than 3 red
+            if draws.iter().any(|seen: &Draw| seen.color == color) {
+                return Err(ParseError::new(line, color, "color listed twice"));
+            }
+
+            draws.push(Draw {
+                count,
+                color: color.to_owned(),
+            });
+        }
+
+        Ok(Round { draws })
+    }
+
+    /// Every color shown in any round, alphabetically
+    pub fn colors(&self) -> BTreeSet<&str> {
+        self.rounds
+            .iter()
+            .flat_map(|round| &round.draws)
+            .map(|draw| draw.color.as_str())
+            .collect()
+    }
+
+    /// Most cubes of `color` shown in one round, the fewest the bag can have had
+    pub fn fewest(&self, color: &str) -> u32 {
+        self.rounds
+            .iter()
+            .map(|round| round.count(color))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether every round could have come out of `bag`, colors it doesn't list it has none of
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.colors()
+            .into_iter()
+            .all(|color| self.fewest(color) <= bag.get(color))
+    }
+
+    /// The fewest cubes of every color the game shows multiplied together,
+    /// colors that never come up don't multiply it by 0
+    ///
+    /// With any number of colors this can outgrow even a `u64`, that's an error rather than a wrapped answer
+    pub fn power(&self) -> Result<u64> {
+        self.power_of(self.colors())
+    }
+
+    /// `power` over just the colors `bag` lists, what part two asks when the bag has its own colors
+    pub fn power_in(&self, bag: &Bag) -> Result<u64> {
+        self.power_of(
+            self.colors()
+                .into_iter()
+                .filter(|color| bag.cubes().any(|(listed, _)| listed == *color)),
+        )
+    }
+
+    fn power_of<'a>(&self, colors: impl IntoIterator<Item = &'a str>) -> Result<u64> {
+        colors
+            .into_iter()
+            .try_fold(1u64, |power, color| {
+                // A color only ever drawn as `0 red` doesn't multiply it by 0 either
+                power.checked_mul(u64::from(self.fewest(color).max(1)))
+            })
+            .ok_or_else(|| format!("Game {}: power overflows", self.id).into())
+    }
+
+    /// Same game with every round canonical, rounds keep their order since that's when they were played
+    pub fn canonical(&self) -> Game {
+        Game {
+            id: self.id,
+            rounds: self.rounds.iter().map(Round::canonical).collect(),
+        }
+    }
+}
+
+/// Written the way the puzzle input is, `Game 1: 3 blue, 4 red; 1 red`,
+/// `Game::new` reads it back as long as no round is empty and colors are single words
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rounds: Vec<String> = self
+            .rounds
+            .iter()
+            .map(|round| {
+                let draws: Vec<String> = round
+                    .draws
+                    .iter()
+                    .map(|draw| format!("{} {}", draw.count, draw.color))
+                    .collect();
+                draws.join(", ")
+            })
+            .collect();
+
+        write!(f, "Game {}: {}", self.id, rounds.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let game = Game::new("Game 12: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+
+        assert_eq!(game.id, 12);
+        assert_eq!(game.rounds.len(), 3);
+        assert_eq!(
+            game.rounds[0].draws,
+            vec![
+                Draw {
+                    count: 3,
+                    color: String::from("blue")
+                },
+                Draw {
+                    count: 4,
+                    color: String::from("red")
+                },
+            ]
+        );
+        assert_eq!(game.rounds[1].count("green"), 2);
+        assert_eq!(game.rounds[2].count("red"), 0);
+        assert_eq!(
+            game.to_string(),
+            "Game 12: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"
+        );
+    }
+
+    #[test]
+    fn test_fewest() {
+        let game = Game::new("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green").unwrap();
+
+        assert_eq!(game.fewest("red"), 4);
+        assert_eq!(game.fewest("green"), 2);
+        assert_eq!(game.fewest("blue"), 6);
+        assert_eq!(game.fewest("purple"), 0);
+        assert_eq!(game.power().unwrap(), 48);
+
+        let bag = Bag::new().with("red", 4).with("green", 2).with("blue", 6);
+        assert!(game.is_possible(&bag));
+        assert!(!game.is_possible(&Bag::new().with("red", 4).with("green", 2)));
+    }
+
+    #[test]
+    fn test_any_color() {
+        let game = Game::new("Game 7: 2 purple, 1 red; 5 purple").unwrap();
+
+        assert_eq!(
+            game.colors().into_iter().collect::<Vec<_>>(),
+            vec!["purple", "red"]
+        );
+        assert_eq!(game.power().unwrap(), 5);
+
+        assert!(!game.is_possible(&Bag::puzzle()));
+        assert!(game.is_possible(&Bag::puzzle().with("purple", 5)));
+    }
+
+    #[test]
+    fn test_power_in() {
+        let game = Game::new("Game 7: 2 purple, 1 red; 5 purple, 3 blue").unwrap();
+
+        assert_eq!(game.power().unwrap(), 15);
+        assert_eq!(game.power_in(&Bag::puzzle()).unwrap(), 3);
+        assert_eq!(game.power_in(&Bag::new().with("purple", 1)).unwrap(), 5);
+        // Nothing the game shows, nothing to multiply
+        assert_eq!(game.power_in(&Bag::new().with("green", 1)).unwrap(), 1);
+
+        let game = Game::new("Game 1: 0 red, 3 blue; 2 green").unwrap();
+        assert_eq!(game.power().unwrap(), 6);
+        assert_eq!(game.power_in(&Bag::puzzle()).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_power_overflow() {
+        // Past what a `u32` holds
+        let game = Game::new("Game 1: 2000 a, 2000 b, 2000 c").unwrap();
+        assert_eq!(game.power().unwrap(), 8_000_000_000);
+
+        let game = Game::new("Game 9: 70000 a, 70000 b, 70000 c, 70000 d").unwrap();
+        assert_eq!(
+            game.power().unwrap_err().to_string(),
+            "Game 9: power overflows"
+        );
+    }
+
+    #[test]
+    fn test_unexpected_field() {
+        let error = Game::new("Game 1: 3 dark blue, 4 red").unwrap_err();
+        assert_eq!(
+            (error.column, error.token.as_str(), error.message.as_str()),
+            (16, "blue", "unexpected field")
+        );
+
+        let error = Game::new("Game 1: 4 red; 3 blue 7").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (23, "7"));
+    }
+
+    #[test]
+    fn test_color_listed_twice() {
+        let error = Game::new("Game 1: 4294967295 red, 1 red").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (27, "color listed twice")
+        );
+
+        // Once per round, the same color in another round is fine
+        let game = Game::new("Game 1: 4294967295 red; 1 red").unwrap();
+        assert_eq!(game.fewest("red"), u32::MAX);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let game = Game::new("Game  3 :4 red,3 blue;  2 green ,1 red, 6 blue; 0 red").unwrap();
+        let canonical = game.canonical();
+
+        assert_eq!(
+            canonical.to_string(),
+            "Game 3: 3 blue, 4 red; 6 blue, 2 green, 1 red; 0 red"
+        );
+        for (round, sorted) in game.rounds.iter().zip(&canonical.rounds) {
+            assert!(round.same_draws(sorted));
+        }
+        // Nothing the puzzle asks about changes
+        assert_eq!(canonical.colors(), game.colors());
+        assert_eq!(canonical.power().unwrap(), game.power().unwrap());
+        assert_eq!(canonical.canonical(), canonical);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let games = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 purple",
+            "Game 100: 0 red; 2 red, 0 blue; 7 teal, 1 blue",
+        ];
+
+        for line in games {
+            let game = Game::new(line).unwrap();
+            assert_eq!(Game::new(&game.to_string()).unwrap(), game);
+
+            let canonical = game.canonical();
+            assert_eq!(Game::new(&canonical.to_string()).unwrap(), canonical);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use aoc_common::{read_input, Answer, ParseError, ParseResult, Result, Solver};
+
+pub mod bag;
+pub mod game;
+pub mod plausibility;
+pub mod query;
+
+use bag::Bag;
+use game::Game;
+
+pub fn parse_input(input: &str) -> ParseResult<Vec<Game>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| Game::new(line).map_err(|e| e.on_line(i + 1)))
+        .collect()
+}
+
+/// Every game rewritten canonically one per line, in the order they came, see `Round::canonical`
+///
+/// `--fmt` overwrites the file with this, so a game that wouldn't read back with the same draws is an error
+pub fn format_input(input: &str) -> ParseResult<String> {
+    let mut formatted = String::with_capacity(input.len());
+
+    for (i, line) in input.lines().enumerate() {
+        let game = Game::new(line).map_err(|e| e.on_line(i + 1))?;
+        let written = game.canonical().to_string();
+
+        let unchanged = Game::new(&written).is_ok_and(|read| {
+            read.id == game.id
+                && read.rounds.len() == game.rounds.len()
+                && read
+                    .rounds
+                    .iter()
+                    .zip(&game.rounds)
+                    .all(|(read, round)| read.same_draws(round))
+        });
+        if !unchanged {
+            return Err(
+                ParseError::new(line, line, "game can't be written back unchanged").on_line(i + 1),
+            );
+        }
+
+        formatted.push_str(&written);
+        formatted.push('\n');
+    }
+
+    Ok(formatted)
+}
+
+pub struct Solution {
+    games: Vec<Game>,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            games: parse_input(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(sum_of_possible_ids(&self.games, &Bag::puzzle()).into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(sum_of_powers(&self.games, Game::power)?.into())
+    }
+}
+
+fn sum_of_possible_ids(games: &[Game], bag: &Bag) -> u32 {
+    games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .sum()
+}
+
+fn sum_of_powers(games: &[Game], power: impl Fn(&Game) -> Result<u64>) -> Result<u64> {
+    games.iter().try_fold(0u64, |sum, game| {
+        sum.checked_add(power(game)?)
+            .ok_or_else(|| "Sum of powers overflows".into())
+    })
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u32> {
+    Ok(sum_of_possible_ids(&parse_input(input)?, &Bag::puzzle()))
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u64> {
+    sum_of_powers(&parse_input(input)?, Game::power)
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u64> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+/// Part one for a bag other than the puzzle's
+pub fn solve_part_one_with_bag_str(input: &str, bag: &Bag) -> Result<u32> {
+    Ok(sum_of_possible_ids(&parse_input(input)?, bag))
+}
+
+/// Part two over the colors of a bag other than the puzzle's, see `Game::power_in`
+pub fn solve_part_two_with_bag_str(input: &str, bag: &Bag) -> Result<u64> {
+    sum_of_powers(&parse_input(input)?, |game| game.power_in(bag))
+}
+
+pub fn solve_part_one_with_bag(filename: &str, bag: &Bag) -> Result<u32> {
+    solve_part_one_with_bag_str(&read_input(filename)?, bag)
+}
+
+pub fn solve_part_two_with_bag(filename: &str, bag: &Bag) -> Result<u64> {
+    solve_part_two_with_bag_str(&read_input(filename)?, bag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_is_possible() {
+        let correct_game_1 = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        assert!(Game::new(correct_game_1)
+            .unwrap()
+            .is_possible(&Bag::puzzle()));
+
+        let incorrect_game_2 =
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+
+        assert!(!Game::new(incorrect_game_2)
+            .unwrap()
+            .is_possible(&Bag::puzzle()));
+    }
+
+    #[test]
+    fn test_power() {
+        let correct_game_1 = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        assert_eq!(Game::new(correct_game_1).unwrap().power().unwrap(), 48);
+
+        let game_with_zero_red = "Game 1: 3 blue; 2 green, 6 blue; 2 green";
+
+        assert_eq!(Game::new(game_with_zero_red).unwrap().power().unwrap(), 12);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Game::new("Game 1: 3 blue, x red").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (17, "x"));
+
+        let error = Game::new("Game 1: 3 blue; ").unwrap_err();
+        assert_eq!(error.message, "expected a cube count");
+
+        let error = Game::new("Game 1 3 blue").unwrap_err();
+        assert_eq!(error.column, 14);
+
+        let error = parse_input("Game 1: 3 blue\n\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+
+    #[test]
+    fn test_part_one_solution() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_other_bags() {
+        // Game 3 needs 20 red and 13 green, game 4 needs 14 red and 15 blue
+        let bag = Bag::puzzle().with("red", 20).with("blue", 15);
+        assert_eq!(solve_part_one_with_bag_str(TEST_INPUT, &bag).unwrap(), 15);
+        // Every game shows more than red
+        let red = Bag::new().with("red", 20);
+        assert_eq!(solve_part_one_with_bag_str(TEST_INPUT, &red).unwrap(), 0);
+
+        // The puzzle's own colors give the usual part two
+        let part_two = |bag: &Bag| solve_part_two_with_bag_str(TEST_INPUT, bag).unwrap();
+        assert_eq!(part_two(&Bag::puzzle()), 2286);
+        // 4 + 1 + 20 + 14 + 6 red cubes at least
+        assert_eq!(part_two(&Bag::new().with("red", 1)), 45);
+    }
+
+    #[test]
+    fn test_part_two_solution() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 2286);
+    }
+
+    #[test]
+    fn test_format_input() {
+        let input = "Game 2:1 red,2 blue\nGame 1: 3 blue; 4 red, 0 green, 1 blue";
+        let formatted = format_input(input).unwrap();
+
+        assert_eq!(
+            formatted,
+            "Game 2: 2 blue, 1 red\nGame 1: 3 blue; 1 blue, 0 green, 4 red\n"
+        );
+        assert_eq!(format_input(&formatted).unwrap(), formatted);
+
+        // Formatting can't change an answer
+        let formatted = format_input(TEST_INPUT).unwrap();
+        let canonical: Vec<Game> = parse_input(TEST_INPUT)
+            .unwrap()
+            .iter()
+            .map(Game::canonical)
+            .collect();
+        assert_eq!(parse_input(&formatted).unwrap(), canonical);
+        assert_eq!(solve_part_one_str(&formatted).unwrap(), 8);
+        assert_eq!(solve_part_two_str(&formatted).unwrap(), 2286);
+
+        let error = format_input("Game 1: 3 blue\nGame 2: blue").unwrap_err();
+        assert_eq!(error.line, 2);
+
+        // Nothing gets dropped on the way through, the whole input is refused instead
+        let error = format_input("Game 1: 2 red\nGame 2: 3 dark blue, 4 red; 2 green").unwrap_err();
+        assert_eq!(
+            (error.line, error.token.as_str(), error.message.as_str()),
+            (2, "blue", "unexpected field")
+        );
+    }
+}
@@ -1,182 +1,159 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-use std::collections::HashMap;
-
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
+use std::env;
+use std::fs;
+use std::process;
+
+use aoc_common::{parse_token, read_input, render_error, Part, Result};
+use day_2::bag::Bag;
+use day_2::plausibility::Simulation;
+use day_2::query::Query;
+
+const USAGE: &str = "Usage: day-2 [--part <1|2>] [--bag <color=count,...>] [--bag-file <file>] \
+     [--query <query>] [--simulate <games> [--seed <n>]] [--fmt <file>]";
+
+#[derive(Default)]
+struct Options {
+    part: Option<Part>,
+    bag: Option<Bag>,
+    bag_file: Option<String>,
+    query: Option<Query>,
+    simulate: Option<usize>,
+    seed: Option<u64>,
+    fmt: Option<String>,
 }
 
-fn solve(
-    f: fn(&str) -> Result<Option<u32>, Box<dyn std::error::Error>>,
-    filename: &str,
-) -> Result<u32, Box<dyn std::error::Error>> {
-    let lines = read_lines(filename)?;
+fn parse_options(args: &[String]) -> Result<Option<Options>> {
+    let mut options = Options::default();
+    let flags = args.len() / 2;
+    let mut args = args.iter();
 
-    let mut answer = 0;
-    for line in lines {
-        if let Some(game_id) = f(&line?)? {
-            answer += game_id;
+    while let Some(flag) = args.next() {
+        let Some(value) = args.next() else {
+            return Ok(None);
+        };
+
+        match flag.as_str() {
+            "--part" => match value.as_str() {
+                "1" => options.part = Some(Part::One),
+                "2" => options.part = Some(Part::Two),
+                _ => return Ok(None),
+            },
+            // `--bag red=12,green=13,blue=14,purple=3` checks part one against another bag
+            "--bag" => options.bag = Some(Bag::parse_spec(value)?),
+            // Same with one `<color> <count>` per line of a file
+            "--bag-file" => options.bag_file = Some(value.clone()),
+            // `--query "missing green"`, see `Query::parse` for the others
+            "--query" => options.query = Some(Query::parse(value).map_err(|e| e.on_line(1))?),
+            // `--simulate 100` makes up that many games out of the bag instead of reading any
+            "--simulate" => {
+                options.simulate = Some(parse_token(value, value).map_err(|e| e.on_line(1))?)
+            }
+            "--seed" => options.seed = Some(parse_token(value, value).map_err(|e| e.on_line(1))?),
+            // `--fmt input.txt` rewrites the games in the file canonically
+            "--fmt" => options.fmt = Some(value.clone()),
+            _ => return Ok(None),
         }
     }
 
-    Ok(answer)
-}
-
-fn _parse_line_part_one(line: &str) -> Result<Option<u32>, Box<dyn std::error::Error>> {
-    let mut cube_constraints: HashMap<String, u32> = HashMap::new();
-    cube_constraints.insert(String::from("red"), 12);
-    cube_constraints.insert(String::from("green"), 13);
-    cube_constraints.insert(String::from("blue"), 14);
-
-    // Just in case the elf is funny and the input is not sorted by the game id
-    let game_id = line
-        .split(":")
-        .next()
-        .expect("The input is always valid")
-        .split_whitespace()
-        .last()
-        .expect("The input is always valid")
-        .parse::<u32>()?;
-
-    let modified_input = line.replace(";", ",");
-    let game_results: Vec<&str> = modified_input
-        .split(":")
-        .last()
-        .expect("The input is always valid")
-        .trim()
-        .split(", ")
-        .collect();
-    for result in game_results {
-        let mut parts = result.split_whitespace();
-
-        let cube_count = parts
-            .next()
-            .expect("Input is always valid")
-            .parse::<u32>()?;
-        let cube_color = parts.last().expect("Input is always valid");
-
-        let max_cube_count = cube_constraints
-            .get(cube_color)
-            .expect("The color is always correct");
-
-        if cube_count > *max_cube_count {
-            return Ok(None);
-        }
+    if options.bag.is_some() && options.bag_file.is_some() {
+        return Err("--bag and --bag-file can't be used together".into());
+    }
+    // A query brings its own bag if it needs one
+    if options.query.is_some()
+        && (options.part.is_some()
+            || options.bag.is_some()
+            || options.bag_file.is_some()
+            || options.simulate.is_some())
+    {
+        return Err("--query can't be used with --part, --bag, --bag-file or --simulate".into());
+    }
+    if options.simulate.is_some() && options.part.is_some() {
+        return Err("--simulate can't be used with --part".into());
+    }
+    if options.seed.is_some() && options.simulate.is_none() {
+        return Err("--seed only goes with --simulate".into());
+    }
+    if options.fmt.is_some() && flags > 1 {
+        return Err("--fmt can't be used with other flags".into());
     }
 
-    return Ok(Some(game_id));
+    Ok(Some(options))
 }
 
-fn parse_line_part_two(line: &str) -> Result<Option<u32>, Box<dyn std::error::Error>> {
-    // Just in case the elf is funny and the input is not sorted by the game id
-    let _game_id = line
-        .split(":")
-        .next()
-        .expect("The input is always valid")
-        .split_whitespace()
-        .last()
-        .expect("The input is always valid")
-        .parse::<u32>()?;
-
-    let modified_input = line.replace(";", ",");
-    let game_results: Vec<&str> = modified_input
-        .split(":")
-        .last()
-        .expect("The input is always valid")
-        .trim()
-        .split(", ")
-        .collect();
-
-    let mut min_number_of_red = 0;
-    let mut min_number_of_blue = 0;
-    let mut min_number_of_green = 0;
-
-    for result in game_results {
-        let mut parts = result.split_whitespace();
-
-        let cube_count = parts
-            .next()
-            .expect("Input is always valid")
-            .parse::<u32>()?;
-        let cube_color = parts.last().expect("Input is always valid");
-
-        match cube_color {
-            "red" => {
-                if cube_count > min_number_of_red {
-                    min_number_of_red = cube_count;
-                }
-            }
-            "blue" => {
-                if cube_count > min_number_of_blue {
-                    min_number_of_blue = cube_count;
-                }
-            }
+/// Leaves the file alone if it already is canonical
+fn format_file(filename: &str) -> Result<String> {
+    let input = read_input(filename)?;
+    let formatted = day_2::format_input(&input).map_err(|e| e.in_file(filename))?;
 
-            "green" => {
-                if cube_count > min_number_of_green {
-                    min_number_of_green = cube_count
-                }
-            }
-
-            _ => unreachable!(),
-        }
+    if formatted == input {
+        return Ok(format!("{} is already formatted", filename));
     }
-    // If the game had no cubes of some color, don't multiply by 0
-    min_number_of_red = std::cmp::max(min_number_of_red, 1);
-    min_number_of_blue = std::cmp::max(min_number_of_blue, 1);
-    min_number_of_green = std::cmp::max(min_number_of_green, 1);
-
-    let power_of_set = min_number_of_blue * min_number_of_green * min_number_of_red;
-
-    return Ok(Some(power_of_set));
-}
-
-fn main() {
-    let answer = solve(parse_line_part_two, "input.txt").unwrap();
-    println!("The answer is {}", answer);
+    fs::write(filename, formatted)?;
+    Ok(format!("Formatted {}", filename))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_line_part_one() {
-        let correct_game_1 = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        assert_eq!(parse_line_part_one(correct_game_1).unwrap(), Some(1));
-
-        let incorrect_game_2 =
-            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+fn run(filename: &str, options: Options) -> Result<String> {
+    if let Some(file) = &options.fmt {
+        return format_file(file);
+    }
 
-        assert_eq!(parse_line_part_one(incorrect_game_2).unwrap(), None);
+    if let Some(query) = &options.query {
+        let games = day_2::parse_input(&read_input(filename)?)?;
+        return Ok(query.run(&games));
     }
 
-    #[test]
-    fn test_parse_line_part_two() {
-        let correct_game_1 = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        assert_eq!(parse_line_part_two(correct_game_1).unwrap(), Some(48));
+    let bag = match (options.bag, &options.bag_file) {
+        (Some(bag), _) => Some(bag),
+        (None, Some(file)) => Some(Bag::from_file(file)?),
+        (None, None) => None,
+    };
 
-        let game_with_zero_red = "Game 1: 3 blue; 2 green, 6 blue; 2 green";
+    if let Some(count) = options.simulate {
+        let mut simulation = Simulation::new(bag.as_ref().unwrap_or(&Bag::puzzle()));
+        if let Some(seed) = options.seed {
+            simulation = simulation.seed(seed);
+        }
 
-        assert_eq!(parse_line_part_two(game_with_zero_red).unwrap(), Some(12));
+        let games: Vec<String> = simulation
+            .games(count)
+            .iter()
+            .map(|game| game.to_string())
+            .collect();
+        return Ok(games.join("\n"));
     }
 
-    #[test]
-    fn test_part_one_solution() {
-        let input_filename = "test.txt";
-        assert_eq!(solve(parse_line_part_one, input_filename).unwrap(), 8);
+    // A bag is part one's question unless part two is asked for, then it picks the colors
+    match (bag, options.part) {
+        (Some(bag), None | Some(Part::One)) => {
+            day_2::solve_part_one_with_bag(filename, &bag).map(u64::from)
+        }
+        (Some(bag), Some(Part::Two)) => day_2::solve_part_two_with_bag(filename, &bag),
+        (None, Some(Part::One)) => day_2::solve_part_one(filename).map(u64::from),
+        (None, _) => day_2::solve_part_two(filename),
     }
+    .map(|answer| format!("The answer is {}", answer))
+}
 
-    #[test]
-    fn test_part_two_solution() {
-        let input_filename = "test.txt";
+fn main() {
+    let filename = "input.txt";
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_options(&args) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+        Err(e) => {
+            eprintln!("{}", render_error(e.as_ref(), "arguments"));
+            process::exit(2);
+        }
+    };
 
-        assert_eq!(solve(parse_line_part_two, input_filename).unwrap(), 2286);
+    match run(filename, options) {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("{}", render_error(e.as_ref(), filename));
+            process::exit(1);
+        }
     }
 }
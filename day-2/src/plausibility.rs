@@ -0,0 +1,461 @@
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+use aoc_common::Rng;
+
+use crate::bag::Bag;
+use crate::game::{Draw, Game, Round};
+
+/// Likelihoods this close count as a tie
+const EPSILON: f64 = 1e-12;
+
+/// Most cubes a simulated round draws unless told otherwise
+pub const DRAW: u32 = 100;
+
+/// Up to this many factors `ln_choose` multiplies out, past it Stirling is closer than the rounding
+const EXACT: u64 = 1000;
+
+/// ln of `n!`, Stirling's series for big `n`
+fn ln_factorial(n: u64) -> f64 {
+    if n <= EXACT {
+        return (2..=n).map(|i| (i as f64).ln()).sum();
+    }
+
+    let n = n as f64;
+    n * n.ln() - n + 0.5 * (std::f64::consts::TAU * n).ln() + 1.0 / (12.0 * n)
+        - 1.0 / (360.0 * n.powi(3))
+}
+
+/// ln of `n` choose `k`, minus infinity when there aren't `k` to choose from
+fn ln_choose(n: u64, k: u64) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+
+    let k = k.min(n - k);
+    if k > EXACT {
+        return ln_factorial(n) - ln_factorial(k) - ln_factorial(n - k);
+    }
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum()
+}
+
+/// Bags and rounds can hold more than a `u32` of cubes between their colors
+fn total(bag: &Bag) -> u64 {
+    bag.cubes().map(|(_, count)| u64::from(count)).sum()
+}
+
+/// ln of `round_probability`, doesn't underflow for long games
+pub fn ln_round_probability(round: &Round, bag: &Bag) -> f64 {
+    let colors: BTreeSet<&str> = round.draws.iter().map(|draw| draw.color.as_str()).collect();
+    let drawn: u64 = round.draws.iter().map(|draw| u64::from(draw.count)).sum();
+    if drawn > total(bag) {
+        return f64::NEG_INFINITY;
+    }
+
+    // Multivariate hypergeometric: ways to pick each color's cubes out of all ways to pick that many
+    colors
+        .into_iter()
+        .map(|color| ln_choose(bag.get(color).into(), round.count(color).into()))
+        .sum::<f64>()
+        - ln_choose(total(bag), drawn)
+}
+
+/// Chance of pulling exactly this handful out of `bag`, cubes are drawn without putting them back
+pub fn round_probability(round: &Round, bag: &Bag) -> f64 {
+    ln_round_probability(round, bag).exp()
+}
+
+/// Cubes go back into the bag between rounds, so the rounds are independent
+pub fn ln_likelihood(game: &Game, bag: &Bag) -> f64 {
+    game.rounds
+        .iter()
+        .map(|round| ln_round_probability(round, bag))
+        .sum()
+}
+
+pub fn likelihood(game: &Game, bag: &Bag) -> f64 {
+    ln_likelihood(game, bag).exp()
+}
+
+/// The bag of at most `max_total` cubes that makes the game most likely, with its likelihood,
+/// the smaller bag on ties, `None` if the game needs more cubes than that
+///
+/// Only colors the game shows are considered, any other cube would just get in the way.
+/// Without a cap the likelihood can keep growing with the bag, e.g. for "2 red; 2 blue"
+pub fn most_likely_bag(game: &Game, max_total: u32) -> Option<(Bag, f64)> {
+    let colors: Vec<&str> = game.colors().into_iter().collect();
+    let mut counts: Vec<u32> = colors.iter().map(|color| game.fewest(color)).collect();
+    let mut total: u64 = counts.iter().map(|&count| u64::from(count)).sum();
+    if total > u64::from(max_total) {
+        return None;
+    }
+
+    // For a fixed total the `ln_choose(total, drawn)` of every round is the same whatever the colors,
+    // and what's left is a sum of one concave term per color. So handing out cubes one at a time to
+    // whichever color gains the most gives the best bag of every size on the way up.
+    let drawn: Vec<Vec<u64>> = colors
+        .iter()
+        .map(|color| {
+            game.rounds
+                .iter()
+                .map(|round| round.count(color).into())
+                .collect()
+        })
+        .collect();
+    let sizes: Vec<u64> = drawn
+        .iter()
+        .fold(vec![0; game.rounds.len()], |sizes, counts| {
+            sizes
+                .iter()
+                .zip(counts)
+                .map(|(size, count)| size + count)
+                .collect()
+        });
+
+    // Only the change from the smallest bag matters to pick the best one
+    let mut ln = 0.0;
+    let mut best = (counts.clone(), ln);
+
+    while total < u64::from(max_total) {
+        // Later colors win ties so earlier ones stay small, any of them would do
+        let mut grow = 0;
+        let mut most = f64::NEG_INFINITY;
+        for (i, count) in counts.iter().enumerate() {
+            let gain = gain(&drawn[i], u64::from(*count));
+            if gain > most - EPSILON {
+                grow = i;
+                most = most.max(gain);
+            }
+        }
+
+        ln += most - gain(&sizes, total);
+        counts[grow] += 1;
+        total += 1;
+        if ln > best.1 + EPSILON {
+            best = (counts.clone(), ln);
+        }
+    }
+
+    let bag = colors
+        .iter()
+        .zip(best.0)
+        .fold(Bag::new(), |bag, (color, count)| bag.with(color, count));
+    let likelihood = likelihood(game, &bag);
+    Some((bag, likelihood))
+}
+
+/// How much going from `count` to `count + 1` cubes adds to the ln of the ways to pick each of `drawn`,
+/// `count` is never less than any of them
+fn gain(drawn: &[u64], count: u64) -> f64 {
+    drawn
+        .iter()
+        .filter(|&&drawn| drawn > 0)
+        .map(|&drawn| ((count + 1) as f64 / (count + 1 - drawn) as f64).ln())
+        .sum()
+}
+
+/// Makes up game logs by drawing from a bag, e.g. to see what a real bag's games look like
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    bag: Bag,
+    rounds: RangeInclusive<usize>,
+    draw: RangeInclusive<u32>,
+    seed: u64,
+}
+
+impl Simulation {
+    /// One to six rounds per game like the puzzle input, each drawing anywhere from one cube to the whole bag,
+    /// or to `DRAW` cubes for bigger bags
+    pub fn new(bag: &Bag) -> Self {
+        Simulation {
+            bag: bag.clone(),
+            rounds: 1..=6,
+            draw: 1..=total(bag).clamp(1, u64::from(DRAW)) as u32,
+            seed: 2023,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// If the range is empty or allows a game without rounds, `Game::new` couldn't read that back
+    pub fn rounds(mut self, rounds: RangeInclusive<usize>) -> Self {
+        assert!(
+            *rounds.start() >= 1 && rounds.start() <= rounds.end(),
+            "games need at least one round, got {:?}",
+            rounds
+        );
+        self.rounds = rounds;
+        self
+    }
+
+    /// Cubes per round, capped by what's in the bag, every cube drawn costs a pass over the colors
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty or allows a round without cubes, same as `rounds`
+    pub fn draw(mut self, draw: RangeInclusive<u32>) -> Self {
+        assert!(
+            *draw.start() >= 1 && draw.start() <= draw.end(),
+            "rounds need at least one cube, got {:?}",
+            draw
+        );
+        self.draw = draw;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn round(&self, rng: &mut Rng) -> Round {
+        let mut left: Vec<(&str, u64)> = self
+            .bag
+            .cubes()
+            .map(|(color, count)| (color, u64::from(count)))
+            .collect();
+        let mut total = total(&self.bag);
+        let size = u64::from(rng.range(self.draw.clone())).min(total);
+
+        // One cube at a time, each color as likely as how many of its cubes are still in the bag
+        let mut drawn = vec![0; left.len()];
+        for _ in 0..size {
+            let mut pick = rng.next_u64() % total;
+            let mut i = 0;
+            while pick >= left[i].1 {
+                pick -= left[i].1;
+                i += 1;
+            }
+
+            left[i].1 -= 1;
+            drawn[i] += 1;
+            total -= 1;
+        }
+
+        let draws = left
+            .iter()
+            .zip(drawn)
+            .filter(|(_, count)| *count > 0)
+            .map(|((color, _), count)| Draw {
+                count,
+                color: (*color).to_owned(),
+            })
+            .collect();
+
+        Round { draws }
+    }
+
+    /// Games numbered from 1, an empty bag has nothing to show so it gives no games
+    pub fn games(&self, count: usize) -> Vec<Game> {
+        if total(&self.bag) == 0 {
+            return Vec::new();
+        }
+
+        let mut rng = Rng::new(self.seed);
+        (1..=count as u32)
+            .map(|id| {
+                let rounds =
+                    self.rounds.start() + rng.below(self.rounds.end() - self.rounds.start() + 1);
+                Game {
+                    id,
+                    rounds: (0..rounds).map(|_| self.round(&mut rng)).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    fn game(line: &str) -> Game {
+        Game::new(line).unwrap()
+    }
+
+    #[test]
+    fn test_ln_choose() {
+        assert_eq!(ln_choose(5, 0), 0.0);
+        assert!((ln_choose(5, 2).exp() - 10.0).abs() < 1e-9);
+        assert!((ln_choose(52, 5).exp() - 2_598_960.0).abs() < 1e-3);
+        assert_eq!(ln_choose(2, 3), f64::NEG_INFINITY);
+
+        // Stirling against multiplying it all out
+        let exact: f64 = (1..=2500u64)
+            .map(|i| ((2500 + i) as f64 / i as f64).ln())
+            .sum();
+        assert!((ln_choose(5000, 2500) - exact).abs() < 1e-9 * exact);
+        assert!((ln_choose(1 << 32, (1 << 32) - 1) - 32.0 * 2f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_huge_bag() {
+        let bag = Bag::new().with("red", u32::MAX).with("blue", 1);
+
+        // All but the blue one, out of all but one cube
+        let game = game("Game 1: 4294967295 red");
+        assert!((ln_likelihood(&game, &bag) + 32.0 * 2f64.ln()).abs() < 1e-9);
+
+        let games = Simulation::new(&bag).games(3);
+        assert_eq!(games.len(), 3);
+        for game in &games {
+            assert!(game.is_possible(&bag));
+            assert!(game
+                .rounds
+                .iter()
+                .all(|round| { round.draws.iter().map(|draw| draw.count).sum::<u32>() <= DRAW }));
+        }
+    }
+
+    #[test]
+    fn test_round_probability() {
+        let bag = Bag::new().with("red", 2).with("blue", 2);
+        let rounds = &game("Game 1: 1 red, 1 blue; 2 red; 3 red; 5 red").rounds;
+
+        // 2 * 2 of the 6 ways to pick two cubes out of four
+        assert!((round_probability(&rounds[0], &bag) - 4.0 / 6.0).abs() < 1e-9);
+        assert!((round_probability(&rounds[1], &bag) - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(round_probability(&rounds[2], &bag), 0.0);
+        // More cubes than the whole bag
+        assert_eq!(round_probability(&rounds[3], &bag), 0.0);
+
+        let purple = &game("Game 1: 1 purple").rounds[0];
+        assert_eq!(round_probability(purple, &bag), 0.0);
+
+        let game = game("Game 1: 1 red, 1 blue; 2 red");
+        assert!((likelihood(&game, &bag) - 4.0 / 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_impossible_games() {
+        let games = parse_input(include_str!("../test.txt")).unwrap();
+        let bag = Bag::puzzle();
+
+        for game in &games {
+            assert_eq!(likelihood(game, &bag) > 0.0, game.is_possible(&bag));
+        }
+    }
+
+    #[test]
+    fn test_most_likely_bag() {
+        // Anything bigger than one of each makes drawing both less likely
+        let (bag, likelihood) = most_likely_bag(&game("Game 1: 1 red, 1 blue"), 10).unwrap();
+        assert_eq!(bag, Bag::new().with("red", 1).with("blue", 1));
+        assert!((likelihood - 1.0).abs() < 1e-9);
+
+        // Twice as many red as blue seen, so the best bag leans red
+        let (bag, _) = most_likely_bag(&game("Game 1: 2 red, 1 blue; 2 red, 1 blue"), 9).unwrap();
+        assert!(bag.get("red") > bag.get("blue"));
+
+        // Here a bigger bag keeps doing better, the cap decides
+        let (bag, likelihood) = most_likely_bag(&game("Game 1: 2 red; 2 blue"), 9).unwrap();
+        assert_eq!(bag, Bag::new().with("blue", 4).with("red", 5));
+        assert!((likelihood - 60.0 / 1296.0).abs() < 1e-9);
+
+        assert!(most_likely_bag(&game("Game 1: 5 red, 5 blue"), 9).is_none());
+    }
+
+    /// Every bag up to `max_total`, what `most_likely_bag` has to agree with
+    fn exhaustive(game: &Game, max_total: u32) -> Option<f64> {
+        fn search(game: &Game, colors: &[&str], bag: Bag, spare: u32, best: &mut Option<f64>) {
+            let Some((color, rest)) = colors.split_first() else {
+                let ln = ln_likelihood(game, &bag);
+                *best = Some(best.map_or(ln, |best| best.max(ln)));
+                return;
+            };
+            for extra in 0..=spare {
+                let bag = bag.clone().with(color, game.fewest(color) + extra);
+                search(game, rest, bag, spare - extra, best);
+            }
+        }
+
+        let colors: Vec<&str> = game.colors().into_iter().collect();
+        let needed: u32 = colors.iter().map(|color| game.fewest(color)).sum();
+        let mut best = None;
+        if needed <= max_total {
+            search(game, &colors, Bag::new(), max_total - needed, &mut best);
+        }
+        best.map(f64::exp)
+    }
+
+    #[test]
+    fn test_most_likely_bag_is_best() {
+        let mut games = parse_input(include_str!("../test.txt")).unwrap();
+        games.push(game("Game 6: 2 red; 2 blue; 1 green, 1 red"));
+        games.push(game("Game 7: 0 red, 1 blue; 3 teal"));
+
+        for game in &games {
+            for max_total in [8, 25, 45] {
+                let found = most_likely_bag(game, max_total);
+                let expected = exhaustive(game, max_total);
+                assert_eq!(found.is_some(), expected.is_some());
+
+                if let (Some((bag, likelihood)), Some(expected)) = (found, expected) {
+                    assert!((likelihood - expected).abs() <= 1e-9 * expected);
+                    assert!(bag.cubes().map(|(_, count)| count).sum::<u32>() <= max_total);
+                    assert!((super::likelihood(game, &bag) - likelihood).abs() < 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_simulation() {
+        let bag = Bag::new().with("red", 3).with("green", 2).with("blue", 5);
+        let simulation = Simulation::new(&bag).rounds(2..=4).draw(1..=6).seed(9);
+
+        let games = simulation.games(50);
+        assert_eq!(games.len(), 50);
+        assert_eq!(games, simulation.games(50));
+        for (i, game) in games.iter().enumerate() {
+            assert_eq!(game.id, i as u32 + 1);
+            assert!((2..=4).contains(&game.rounds.len()));
+            assert!(game.is_possible(&bag));
+            // The logs parse like the puzzle input does
+            assert_eq!(&Game::new(&game.to_string()).unwrap(), game);
+        }
+
+        assert!(Simulation::new(&Bag::new()).games(3).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one round")]
+    fn test_no_rounds() {
+        Simulation::new(&Bag::puzzle()).rounds(0..=0);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one cube")]
+    fn test_no_cubes() {
+        Simulation::new(&Bag::puzzle()).draw(0..=2);
+    }
+
+    #[test]
+    #[should_panic(expected = "5..=3")]
+    fn test_reversed_range() {
+        Simulation::new(&Bag::puzzle()).draw(RangeInclusive::new(5, 3));
+    }
+
+    #[test]
+    fn test_monte_carlo() {
+        // How often a simulated round matches agrees with the exact probability
+        let bag = Bag::new().with("red", 4).with("blue", 6);
+        let target = &game("Game 1: 2 red, 1 blue").rounds[0];
+        let rounds: Vec<Round> = Simulation::new(&bag)
+            .rounds(1..=1)
+            .draw(3..=3)
+            .games(20_000)
+            .into_iter()
+            .map(|game| game.rounds[0].clone())
+            .collect();
+
+        let hits = rounds
+            .iter()
+            .filter(|round| round.count("red") == 2 && round.count("blue") == 1)
+            .count();
+        let expected = round_probability(target, &bag);
+        assert!((expected - 36.0 / 120.0).abs() < 1e-9);
+        assert!((hits as f64 / rounds.len() as f64 - expected).abs() < 0.02);
+    }
+}
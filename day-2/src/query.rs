@@ -0,0 +1,426 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use aoc_common::{parse_token, ParseError, ParseResult};
+
+use crate::bag::Bag;
+use crate::game::Game;
+use crate::plausibility;
+
+/// Most cubes `likely-bag` tries, it takes a step per cube for every game
+const LIKELY_BAG_CUBES: u32 = 10_000;
+
+const QUERIES: &str = "possible [bag], minimal-bag, stats [color], missing <color>, forcing, \
+     likelihood [bag] or likely-bag <max cubes>";
+
+/// Questions about a whole list of games, see `Query::parse` for how they're written
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// Games that could have come out of the bag
+    Possible(Bag),
+    /// The smallest bag every game could have come out of
+    MinimalBag,
+    /// Max, mean and histogram of the counts drawn, for one color or all of them
+    Stats(Option<String>),
+    /// Games that never show a cube of the color, `0 red` doesn't count
+    Missing(String),
+    /// The round that forced the fewest cubes of each color of each game
+    Forcing,
+    /// How likely each game and each of its rounds is for the bag
+    Likelihood(Bag),
+    /// The bag of at most that many cubes each game most likely came out of
+    LikelyBag(u32),
+}
+
+/// Max, mean and histogram of every count drawn of one color
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStats {
+    pub color: String,
+    pub draws: usize,
+    pub max: u32,
+    pub mean: f64,
+    /// How many draws showed each count
+    pub histogram: BTreeMap<u32, usize>,
+}
+
+/// Where the fewest cubes of one color a game needs come from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forced {
+    pub game: u32,
+    pub color: String,
+    pub count: u32,
+    /// Counting from 1, the first one if several rounds show as many
+    pub round: usize,
+}
+
+pub fn possible<'a>(games: &'a [Game], bag: &Bag) -> Vec<&'a Game> {
+    games.iter().filter(|game| game.is_possible(bag)).collect()
+}
+
+/// Every color with the most cubes any game needs of it
+pub fn minimal_bag(games: &[Game]) -> Bag {
+    games
+        .iter()
+        .flat_map(|game| {
+            game.colors()
+                .into_iter()
+                .map(|color| (color, game.fewest(color)))
+        })
+        .fold(Bag::new(), |bag, (color, fewest)| {
+            let count = bag.get(color).max(fewest);
+            bag.with(color, count)
+        })
+}
+
+fn colors(games: &[Game]) -> BTreeSet<&str> {
+    games.iter().flat_map(Game::colors).collect()
+}
+
+/// Stats for `color`, `None` if no game ever shows it
+pub fn color_stats(games: &[Game], color: &str) -> Option<ColorStats> {
+    let counts: Vec<u32> = games
+        .iter()
+        .flat_map(|game| &game.rounds)
+        .flat_map(|round| &round.draws)
+        .filter(|draw| draw.color == color)
+        .map(|draw| draw.count)
+        .collect();
+
+    let mut histogram = BTreeMap::new();
+    for count in &counts {
+        *histogram.entry(*count).or_insert(0) += 1;
+    }
+
+    Some(ColorStats {
+        color: color.to_owned(),
+        draws: counts.len(),
+        max: *counts.iter().max()?,
+        mean: counts.iter().map(|&count| u64::from(count)).sum::<u64>() as f64
+            / counts.len() as f64,
+        histogram,
+    })
+}
+
+pub fn missing<'a>(games: &'a [Game], color: &str) -> Vec<&'a Game> {
+    games
+        .iter()
+        .filter(|game| game.fewest(color) == 0)
+        .collect()
+}
+
+/// For every game and every color it shows, in game order and then alphabetically
+pub fn forcing(games: &[Game]) -> Vec<Forced> {
+    let mut forced = Vec::new();
+
+    for game in games {
+        for color in game.colors() {
+            let count = game.fewest(color);
+            let round = game
+                .rounds
+                .iter()
+                .position(|round| round.count(color) == count)
+                .map_or(0, |i| i + 1);
+
+            forced.push(Forced {
+                game: game.id,
+                color: color.to_owned(),
+                count,
+                round,
+            });
+        }
+    }
+
+    forced
+}
+
+/// A bag spec inside a query, errors point into the query rather than into the spec on its own
+fn parse_bag(text: &str, spec: &str) -> ParseResult<Bag> {
+    Bag::parse_spec(spec).map_err(|e| {
+        let start = spec
+            .char_indices()
+            .nth(e.column - 1)
+            .map_or(spec.len(), |(i, _)| i);
+        let token = spec
+            .get(start..start + e.token.len())
+            .unwrap_or(&spec[start..]);
+        ParseError::new(text, token, &e.message)
+    })
+}
+
+fn ids(games: &[&Game]) -> String {
+    let ids: Vec<String> = games.iter().map(|game| game.id.to_string()).collect();
+    ids.join("\n")
+}
+
+impl Query {
+    /// One query per line, like `possible red=12,green=13,blue=14`, `missing green` or `stats red`,
+    /// `possible` and `likelihood` on their own use the puzzle's bag
+    pub fn parse(text: &str) -> ParseResult<Self> {
+        let mut tokens = text.split_whitespace();
+        let name = tokens
+            .next()
+            .ok_or_else(|| ParseError::at_end(text, &format!("expected {}", QUERIES)))?;
+
+        let query = match name {
+            "possible" => match tokens.next() {
+                Some(spec) => Query::Possible(parse_bag(text, spec)?),
+                None => Query::Possible(Bag::puzzle()),
+            },
+            "minimal-bag" => Query::MinimalBag,
+            "stats" => Query::Stats(tokens.next().map(str::to_owned)),
+            "missing" => match tokens.next() {
+                Some(color) => Query::Missing(color.to_owned()),
+                None => return Err(ParseError::at_end(text, "expected a color")),
+            },
+            "forcing" => Query::Forcing,
+            "likelihood" => match tokens.next() {
+                Some(spec) => Query::Likelihood(parse_bag(text, spec)?),
+                None => Query::Likelihood(Bag::puzzle()),
+            },
+            "likely-bag" => match tokens.next() {
+                Some(token) => match parse_token::<u32>(text, token)? {
+                    cubes if cubes > LIKELY_BAG_CUBES => {
+                        let message = format!("expected at most {} cubes", LIKELY_BAG_CUBES);
+                        return Err(ParseError::new(text, token, &message));
+                    }
+                    cubes => Query::LikelyBag(cubes),
+                },
+                None => return Err(ParseError::at_end(text, "expected the most cubes to try")),
+            },
+            _ => {
+                let message = format!("unknown query, expected {}", QUERIES);
+                return Err(ParseError::new(text, name, &message));
+            }
+        };
+
+        match tokens.next() {
+            Some(token) => Err(ParseError::new(text, token, "unexpected argument")),
+            None => Ok(query),
+        }
+    }
+
+    /// The answer as text, one game or color per line
+    pub fn run(&self, games: &[Game]) -> String {
+        match self {
+            Query::Possible(bag) => ids(&possible(games, bag)),
+            Query::MinimalBag => minimal_bag(games).to_string(),
+            Query::Stats(color) => {
+                let colors: Vec<&str> = match color {
+                    Some(color) => vec![color],
+                    None => colors(games).into_iter().collect(),
+                };
+
+                let lines: Vec<String> = colors
+                    .into_iter()
+                    .map(|color| match color_stats(games, color) {
+                        Some(stats) => {
+                            let histogram: Vec<String> = stats
+                                .histogram
+                                .iter()
+                                .map(|(count, draws)| format!("{}:{}", count, draws))
+                                .collect();
+                            format!(
+                                "{}: {} draws, max {}, mean {:.2}, histogram {}",
+                                color,
+                                stats.draws,
+                                stats.max,
+                                stats.mean,
+                                histogram.join(" ")
+                            )
+                        }
+                        None => format!("{}: never drawn", color),
+                    })
+                    .collect();
+                lines.join("\n")
+            }
+            Query::Missing(color) => ids(&missing(games, color)),
+            Query::Forcing => {
+                let lines: Vec<String> = forcing(games)
+                    .iter()
+                    .map(|forced| {
+                        format!(
+                            "Game {}: {} {} in round {}",
+                            forced.game, forced.count, forced.color, forced.round
+                        )
+                    })
+                    .collect();
+                lines.join("\n")
+            }
+            Query::Likelihood(bag) => {
+                let lines: Vec<String> = games
+                    .iter()
+                    .map(|game| {
+                        let rounds: Vec<String> = game
+                            .rounds
+                            .iter()
+                            .map(|round| {
+                                format!("{:.3e}", plausibility::round_probability(round, bag))
+                            })
+                            .collect();
+                        format!(
+                            "Game {}: {:.3e}, rounds {}",
+                            game.id,
+                            plausibility::likelihood(game, bag),
+                            rounds.join(" ")
+                        )
+                    })
+                    .collect();
+                lines.join("\n")
+            }
+            Query::LikelyBag(max_total) => {
+                let lines: Vec<String> = games
+                    .iter()
+                    .map(
+                        |game| match plausibility::most_likely_bag(game, *max_total) {
+                            Some((bag, likelihood)) => {
+                                format!("Game {}: {} ({:.3e})", game.id, bag, likelihood)
+                            }
+                            None => {
+                                format!("Game {}: needs more than {} cubes", game.id, max_total)
+                            }
+                        },
+                    )
+                    .collect();
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    fn run(query: &str) -> String {
+        let games = parse_input(TEST_INPUT).unwrap();
+        Query::parse(query).unwrap().run(&games)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Query::parse("possible red=1,blue=2").unwrap(),
+            Query::Possible(Bag::new().with("red", 1).with("blue", 2))
+        );
+        assert_eq!(
+            Query::parse("  possible ").unwrap(),
+            Query::Possible(Bag::puzzle())
+        );
+        assert_eq!(Query::parse("stats").unwrap(), Query::Stats(None));
+        assert_eq!(
+            Query::parse("missing red").unwrap(),
+            Query::Missing(String::from("red"))
+        );
+
+        let error = Query::parse("maximum red").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (1, "maximum"));
+        let error = Query::parse("missing").unwrap_err();
+        assert_eq!(error.message, "expected a color");
+        let error = Query::parse("forcing now").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (9, "now"));
+        let error = Query::parse("possible red=1,blue=x").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (21, "x"));
+        let error = Query::parse("likely-bag 150000").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (12, "expected at most 10000 cubes")
+        );
+        assert!(Query::parse("").is_err());
+
+        assert_eq!(Query::parse("likely-bag 30").unwrap(), Query::LikelyBag(30));
+        let error = Query::parse("likely-bag many").unwrap_err();
+        assert_eq!(error.column, 12);
+        let error = Query::parse("likelihood red=1,blue=x").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (23, "x"));
+    }
+
+    #[test]
+    fn test_possible() {
+        assert_eq!(run("possible"), "1\n2\n5");
+        assert_eq!(run("possible red=20,green=13,blue=15"), "1\n2\n3\n4\n5");
+        assert_eq!(run("possible red=100"), "");
+    }
+
+    #[test]
+    fn test_minimal_bag() {
+        assert_eq!(run("minimal-bag"), "blue=15,green=13,red=20");
+
+        let games = parse_input(TEST_INPUT).unwrap();
+        assert_eq!(possible(&games, &minimal_bag(&games)).len(), games.len());
+    }
+
+    #[test]
+    fn test_stats() {
+        let games = parse_input(TEST_INPUT).unwrap();
+        let stats = color_stats(&games, "red").unwrap();
+
+        assert_eq!((stats.draws, stats.max), (11, 20));
+        assert_eq!(stats.histogram.get(&1), Some(&4));
+        assert!((stats.mean - 61.0 / 11.0).abs() < 1e-9);
+        assert_eq!(color_stats(&games, "purple"), None);
+
+        assert_eq!(
+            run("stats green"),
+            "green: 13 draws, max 13, mean 3.69, histogram 1:2 2:4 3:4 5:1 8:1 13:1"
+        );
+        assert_eq!(run("stats").lines().count(), 3);
+        assert_eq!(run("stats purple"), "purple: never drawn");
+
+        let games = parse_input("Game 1: 4294967295 red; 1 red").unwrap();
+        let stats = color_stats(&games, "red").unwrap();
+        assert!((stats.mean - 2147483648.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_missing() {
+        let games = parse_input(
+            "Game 1: 1 red\nGame 2: 2 blue; 1 green\nGame 3: 1 red, 1 blue\nGame 4: 0 red, 1 blue",
+        )
+        .unwrap();
+
+        let ids: Vec<u32> = missing(&games, "red").iter().map(|game| game.id).collect();
+        assert_eq!(ids, vec![2, 4]);
+        assert_eq!(run("missing red"), "");
+    }
+
+    #[test]
+    fn test_plausibility() {
+        let output = run("likelihood");
+        assert_eq!(output.lines().count(), 5);
+        // Game 3 draws 20 red out of 12
+        assert!(output.contains("Game 3: 0.000e0, rounds 0.000e0"));
+
+        assert_eq!(
+            run("likely-bag 10").lines().next().unwrap(),
+            "Game 1: needs more than 10 cubes"
+        );
+        // Game 2's own fewest cubes are also its most likely bag
+        assert_eq!(
+            run("likely-bag 40").lines().nth(1).unwrap(),
+            "Game 2: blue=4,green=3,red=1 (9.184e-2)"
+        );
+    }
+
+    #[test]
+    fn test_forcing() {
+        let games = parse_input(TEST_INPUT).unwrap();
+        let forced = forcing(&games);
+
+        assert_eq!(forced.len(), 15);
+        assert_eq!(
+            forced[0],
+            Forced {
+                game: 1,
+                color: String::from("blue"),
+                count: 6,
+                round: 2,
+            }
+        );
+
+        let output = run("forcing");
+        assert!(output.contains("Game 3: 20 red in round 1"));
+        assert!(output.contains("Game 4: 15 blue in round 3"));
+    }
+}
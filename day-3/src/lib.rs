@@ -0,0 +1,198 @@
+use aoc_common::{read_input, Answer, ParseError, ParseResult, Result, Solver};
+
+#[derive(Debug, Clone)]
+struct Coordinates {
+    x: usize,
+    y: usize,
+}
+
+#[derive(Debug)]
+struct Symbol {
+    kind: char,
+    location: Coordinates,
+}
+
+#[derive(Debug)]
+struct PartNumber {
+    value: u32,
+    length: usize,
+    location: Coordinates,
+}
+
+impl PartNumber {
+    fn new(line: &str, digit_string: &str, mut location: Coordinates) -> ParseResult<Self> {
+        let length = digit_string.len();
+        let value = digit_string.parse::<u32>().map_err(|_| {
+            ParseError::new(line, digit_string, "part number is too large").on_line(location.y)
+        })?;
+
+        location.x -= length;
+
+        Ok(PartNumber {
+            value,
+            length,
+            location,
+        })
+    }
+}
+
+fn parse_input(input: &str) -> ParseResult<(Vec<PartNumber>, Vec<Symbol>)> {
+    let mut part_numbers: Vec<PartNumber> = Vec::new();
+    let mut symbols: Vec<Symbol> = Vec::new();
+
+    for (y, line) in input.lines().enumerate() {
+        let mut curent_number_buffer: Vec<char> = Vec::new();
+
+        for (x, c) in line.chars().enumerate() {
+            let location = Coordinates { x: x + 1, y: y + 1 };
+
+            if !c.is_ascii_digit() {
+                if !curent_number_buffer.is_empty() {
+                    let number: String = curent_number_buffer.iter().collect();
+
+                    let part_number = PartNumber::new(line, &number, location.clone())?;
+                    part_numbers.push(part_number);
+                    curent_number_buffer.clear();
+                }
+
+                match c {
+                    '.' => {
+                        continue;
+                    }
+                    _ => {
+                        // Part one counts any symbol, part two only cares about the '*' ones
+                        symbols.push(Symbol { kind: c, location });
+                    }
+                }
+            } else {
+                curent_number_buffer.push(c);
+            }
+        }
+
+        if !curent_number_buffer.is_empty() {
+            let location = Coordinates {
+                x: line.len(),
+                y: y + 1,
+            };
+            let number: String = curent_number_buffer.iter().collect();
+            let part_number = PartNumber::new(line, &number, location.clone())?;
+            part_numbers.push(part_number);
+            curent_number_buffer.clear();
+        }
+    }
+
+    Ok((part_numbers, symbols))
+}
+
+fn is_adjacent(part: &PartNumber, symbol: &Coordinates) -> bool {
+    symbol.y >= part.location.y - 1
+        && symbol.y <= part.location.y + 1
+        && symbol.x >= part.location.x - 1
+        && symbol.x <= part.location.x + part.length
+}
+
+pub struct Solution {
+    part_numbers: Vec<PartNumber>,
+    symbols: Vec<Symbol>,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        let (part_numbers, symbols) = parse_input(input)?;
+        Ok(Solution {
+            part_numbers,
+            symbols,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(sum_part_numbers(&self.part_numbers, &self.symbols).into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(sum_gear_ratios(&self.part_numbers, &self.symbols).into())
+    }
+}
+
+fn sum_part_numbers(part_numbers: &[PartNumber], symbols: &[Symbol]) -> u32 {
+    let mut answer = 0;
+
+    for part in part_numbers {
+        for symbol in symbols {
+            if is_adjacent(part, &symbol.location) {
+                answer += part.value;
+                break;
+            }
+        }
+    }
+
+    answer
+}
+
+fn sum_gear_ratios(part_numbers: &[PartNumber], symbols: &[Symbol]) -> u32 {
+    let mut answer = 0;
+
+    for symbol in symbols.iter().filter(|symbol| symbol.kind == '*') {
+        let mut count = 0;
+
+        let mut gear_ratio = 1;
+
+        for part in part_numbers {
+            if count > 2 {
+                break;
+            }
+
+            if is_adjacent(part, &symbol.location) {
+                count += 1;
+                gear_ratio *= part.value;
+            }
+        }
+
+        if count == 2 {
+            answer += gear_ratio;
+        }
+    }
+
+    answer
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u32> {
+    let (part_numbers, symbols) = parse_input(input)?;
+    Ok(sum_part_numbers(&part_numbers, &symbols))
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u32> {
+    let (part_numbers, symbols) = parse_input(input)?;
+    Ok(sum_gear_ratios(&part_numbers, &symbols))
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u32> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_solution_part_one() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 4361);
+    }
+
+    #[test]
+    fn test_solution_part_two() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 467835);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("467..114..\n...*......\n.99999999999*").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+use aoc_common::{parse_token, read_input, Answer, ParseError, ParseResult, Result, Solver};
+
+#[derive(Debug, Clone)]
+struct Card {
+    card_number: usize,
+    value: u32,
+    winning_numbers: Vec<u32>,
+    numbers: Vec<u32>,
+    winning_numbers_count: u32,
+}
+
+impl Card {
+    fn new(line: &str) -> ParseResult<Self> {
+        let (header, card_values) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::at_end(line, "expected `:` after the card number"))?;
+
+        let card_number = match header.split_whitespace().last() {
+            Some(token) => parse_token::<usize>(line, token)?,
+            None => return Err(ParseError::new(line, header, "expected a card number")),
+        };
+
+        let (winning_values, values) = card_values
+            .split_once('|')
+            .ok_or_else(|| ParseError::at_end(line, "expected `|` between the numbers"))?;
+
+        let winning_numbers: Vec<u32> = winning_values
+            .split_whitespace()
+            .map(|x| parse_token::<u32>(line, x))
+            .collect::<ParseResult<Vec<u32>>>()?;
+
+        let numbers: Vec<u32> = values
+            .split_whitespace()
+            .map(|x| parse_token::<u32>(line, x))
+            .collect::<ParseResult<Vec<u32>>>()?;
+
+        Ok(Card {
+            card_number,
+            value: 0,
+            winning_numbers,
+            numbers,
+            winning_numbers_count: 0,
+        })
+    }
+
+    fn count_winning_numbers(&mut self) {
+        for number in &self.numbers {
+            if self.winning_numbers.contains(number) {
+                self.winning_numbers_count += 1
+            }
+        }
+    }
+
+    fn calculate_value(&mut self) {
+        // Assuming numbers can't be duplicates
+        for number in &self.numbers {
+            if self.winning_numbers.contains(number) {
+                if self.value == 0 {
+                    self.value = 1;
+                } else {
+                    self.value *= 2;
+                }
+            }
+        }
+    }
+}
+
+fn parse_input(input: &str) -> ParseResult<Vec<Card>> {
+    let mut cards: Vec<Card> = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let card = Card::new(line).map_err(|e| e.on_line(i + 1))?;
+        cards.push(card);
+    }
+
+    Ok(cards)
+}
+
+pub struct Solution {
+    cards: Vec<Card>,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            cards: parse_input(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(total_points(self.cards.clone()).into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(total_scratchcards(self.cards.clone()).into())
+    }
+}
+
+fn total_points(mut cards: Vec<Card>) -> u32 {
+    cards
+        .iter_mut()
+        .map(|card| {
+            card.calculate_value();
+            card.value
+        })
+        .sum()
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u32> {
+    let cards = parse_input(input)?;
+    Ok(total_points(cards))
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+fn walk(table: &HashMap<usize, Card>, card_number: usize, answer: &mut u32) -> u32 {
+    let Some(current_card) = table.get(&card_number) else {
+        return *answer;
+    };
+
+    let current_winning_number = current_card.winning_numbers_count;
+
+    let range = card_number + (current_winning_number + 1) as usize;
+
+    // Copies past the last card don't exist, so they don't count
+    for tmp_card_number in (card_number + 1..range).filter(|number| table.contains_key(number)) {
+        *answer += 1;
+        walk(table, tmp_card_number, answer);
+    }
+
+    *answer
+}
+
+fn total_scratchcards(mut cards: Vec<Card>) -> u32 {
+    let mut answer = 0;
+    let mut table: HashMap<usize, Card> = HashMap::new();
+
+    for card in cards.iter_mut() {
+        card.count_winning_numbers();
+        table.insert(card.card_number, card.clone());
+        answer += 1;
+    }
+
+    for card in cards.iter() {
+        walk(&table, card.card_number, &mut answer);
+    }
+
+    answer
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u32> {
+    let cards = parse_input(input)?;
+    Ok(total_scratchcards(cards))
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u32> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_solve_part_one() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 13);
+    }
+
+    #[test]
+    fn test_solve_part_two() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 30);
+    }
+
+    #[test]
+    fn test_copies_past_the_end() {
+        let cards = parse_input("Card 1: 1 2 | 1 2").unwrap();
+        assert_eq!(total_scratchcards(cards), 1);
+
+        let cards = parse_input("Card 1: 1 2 | 1 2\nCard 2: 5 | 6").unwrap();
+        assert_eq!(total_scratchcards(cards), 3);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("Card 1: 41 48 | 83 86\n\nCard 3: 1 2 | 3").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error = parse_input("Card 1: 41 4x | 83 86").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (1, 12, "4x")
+        );
+
+        let error = parse_input("Card 1: 41 48 83 86").unwrap_err();
+        assert_eq!(error.column, 20);
+    }
+}
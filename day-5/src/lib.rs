@@ -0,0 +1,340 @@
+use aoc_common::{
+    numbers, parse_token, read_input, Answer, ParseError, ParseResult, Result, Solver,
+};
+
+#[derive(Debug)]
+enum MapVariants {
+    None,
+    SeedSoil,
+    SoilFert,
+    FertWater,
+    WaterLight,
+    LightTemp,
+    TempHum,
+    HumLoc,
+}
+
+#[derive(Debug, PartialEq)]
+struct Map {
+    source: i64,
+    destination: i64,
+    step: i64,
+    diff: i64,
+}
+
+impl Map {
+    fn new(line: &str) -> ParseResult<Self> {
+        let values: Vec<i64> = line
+            .split_whitespace()
+            .map(|value| parse_token::<i64>(line, value))
+            .collect::<ParseResult<Vec<i64>>>()?;
+
+        if values.len() != 3 {
+            return Err(ParseError::new(
+                line,
+                line,
+                "expected destination, source and range length",
+            ));
+        }
+
+        Ok(Map {
+            source: values[1],
+            destination: values[0],
+            step: values[2],
+            diff: (values[1] - values[0]).abs(),
+        })
+    }
+
+    fn get_mapping(&self, input: i64) -> Option<i64> {
+        if input < self.source || input > self.source + self.step {
+            None
+        } else {
+            if self.source > self.destination {
+                Some(input - self.diff)
+            } else {
+                Some(input + self.diff)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Almanac {
+    seeds: Vec<i64>,
+    map_collection: Vec<Vec<Map>>,
+}
+
+fn parse_seeds(line: &str) -> ParseResult<Vec<i64>> {
+    let values = line
+        .strip_prefix("seeds:")
+        .ok_or_else(|| ParseError::new(line, line, "expected the `seeds:` line"))?;
+
+    values
+        .split_whitespace()
+        .map(|value| parse_token::<i64>(line, value))
+        .collect()
+}
+
+fn parse_input(input: &str) -> ParseResult<Almanac> {
+    let mut lines = input.lines().enumerate();
+    let seeds = match lines.next() {
+        Some((_, line)) => parse_seeds(line).map_err(|e| e.on_line(1))?,
+        None => return Err(ParseError::at_end("", "expected the `seeds:` line").on_line(1)),
+    };
+
+    let mut seed_to_soil_maps: Vec<Map> = Vec::new();
+    let mut soil_to_fertilizer_maps: Vec<Map> = Vec::new();
+    let mut fertilizer_to_water_maps: Vec<Map> = Vec::new();
+    let mut water_to_light_maps: Vec<Map> = Vec::new();
+    let mut light_to_temp_maps: Vec<Map> = Vec::new();
+    let mut temp_to_hum_maps: Vec<Map> = Vec::new();
+    let mut hum_to_location_maps: Vec<Map> = Vec::new();
+
+    let mut current_variant = MapVariants::None;
+
+    for (i, line) in lines {
+        match line {
+            "" => continue,
+            "seed-to-soil map:" => current_variant = MapVariants::SeedSoil,
+            "soil-to-fertilizer map:" => current_variant = MapVariants::SoilFert,
+            "fertilizer-to-water map:" => current_variant = MapVariants::FertWater,
+            "water-to-light map:" => current_variant = MapVariants::WaterLight,
+            "light-to-temperature map:" => current_variant = MapVariants::LightTemp,
+            "temperature-to-humidity map:" => current_variant = MapVariants::TempHum,
+            "humidity-to-location map:" => current_variant = MapVariants::HumLoc,
+            _ if line.ends_with("map:") => {
+                return Err(ParseError::new(line, line, "unknown map").on_line(i + 1));
+            }
+            _ => {
+                let map = Map::new(line).map_err(|e| e.on_line(i + 1))?;
+                match current_variant {
+                    MapVariants::SeedSoil => seed_to_soil_maps.push(map),
+                    MapVariants::SoilFert => soil_to_fertilizer_maps.push(map),
+                    MapVariants::FertWater => fertilizer_to_water_maps.push(map),
+                    MapVariants::WaterLight => water_to_light_maps.push(map),
+                    MapVariants::LightTemp => light_to_temp_maps.push(map),
+                    MapVariants::TempHum => temp_to_hum_maps.push(map),
+                    MapVariants::HumLoc => hum_to_location_maps.push(map),
+                    MapVariants::None => {
+                        let error = ParseError::new(line, line, "expected a map header first");
+                        return Err(error.on_line(i + 1));
+                    }
+                }
+            }
+        }
+    }
+
+    let map_collection: Vec<Vec<Map>> = vec![
+        seed_to_soil_maps,
+        soil_to_fertilizer_maps,
+        fertilizer_to_water_maps,
+        water_to_light_maps,
+        light_to_temp_maps,
+        temp_to_hum_maps,
+        hum_to_location_maps,
+    ];
+
+    Ok(Almanac {
+        seeds,
+        map_collection,
+    })
+}
+
+impl Almanac {
+    fn get_location(&self, seed: i64) -> i64 {
+        let mut current_answer = seed;
+
+        for maps in &self.map_collection {
+            for map in maps {
+                match map.get_mapping(current_answer) {
+                    Some(new_answer) => {
+                        current_answer = new_answer;
+                        break;
+                    }
+                    None => continue,
+                }
+            }
+        }
+
+        current_answer
+    }
+
+    fn lowest_location(&self) -> i64 {
+        let mut answer = i64::MAX;
+
+        for &seed in &self.seeds {
+            let current_answer = self.get_location(seed);
+
+            if current_answer < answer {
+                answer = current_answer;
+            }
+        }
+
+        answer
+    }
+
+    fn lowest_location_of_ranges(&self) -> i64 {
+        // Extra slow solution but it works
+        let mut answer = i64::MAX;
+
+        for range in self.seeds.chunks_exact(2) {
+            let (start, step) = (range[0], range[1]);
+            for seed in start..start + step {
+                let current_answer = self.get_location(seed);
+
+                if current_answer < answer {
+                    answer = current_answer;
+                }
+            }
+        }
+
+        answer
+    }
+}
+
+pub struct Solution {
+    almanac: Almanac,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            almanac: parse_input(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(self.almanac.lowest_location().into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(self.almanac.lowest_location_of_ranges().into())
+    }
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<i64> {
+    let almanac = parse_input(input)?;
+    Ok(almanac.lowest_location())
+}
+
+pub fn solve_part_one(filename: &str) -> Result<i64> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+fn _generate_seeds(input: &str) -> Result<Vec<i64>> {
+    // Can't really use this function since storing so many numbers in a Vec
+    // Get's the program to get OOMKilled
+    // Will keep it for the tests and use it's core in the solve function
+    let mut numbers = numbers::<i64>(input);
+
+    let mut seeds: Vec<i64> = Vec::new();
+
+    while let (Some(start), Some(step)) = (numbers.next(), numbers.next()) {
+        for seed in start..start + step {
+            seeds.push(seed)
+        }
+    }
+
+    Ok(seeds)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<i64> {
+    let almanac = parse_input(input)?;
+    Ok(almanac.lowest_location_of_ranges())
+}
+
+pub fn solve_part_two(filename: &str) -> Result<i64> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_map_creation() {
+        let input = "50 98 2";
+
+        let expected_map = Map {
+            source: 98,
+            destination: 50,
+            step: 2,
+            diff: 48,
+        };
+
+        assert_eq!(Map::new(input).unwrap(), expected_map);
+    }
+
+    #[test]
+    fn test_get_mapping() {
+        let input = "50 98 2";
+        let map = Map::new(input).unwrap();
+
+        assert_eq!(map.get_mapping(98), Some(50));
+        assert_eq!(map.get_mapping(99), Some(51));
+        assert_eq!(map.get_mapping(10), None);
+
+        let input = "52 50 48";
+        let map = Map::new(input).unwrap();
+        assert_eq!(map.get_mapping(79), Some(81));
+        assert_eq!(map.get_mapping(55), Some(57));
+
+        let input = "20 10 15";
+        let map = Map::new(input).unwrap();
+
+        assert_eq!(map.get_mapping(11), Some(21));
+        assert_eq!(map.get_mapping(25), Some(35));
+        assert_eq!(map.get_mapping(10), Some(20));
+        assert_eq!(map.get_mapping(9), None);
+    }
+
+    #[test]
+    fn test_solve_part_one() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 35);
+    }
+
+    #[test]
+    fn test_solve_part_two() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 46);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Map::new("50 98").unwrap_err();
+        assert_eq!(error.column, 1);
+
+        let error = Map::new("50 9x8 2").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (4, "9x8"));
+
+        let error = parse_input("").unwrap_err();
+        assert_eq!(error.line, 1);
+
+        let error = parse_input("seeds: 79 14\n\nseed-to-sol map:\n50 98 2").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (3, "unknown map"));
+
+        let error = parse_input("seeds: 79 14\n\n50 98 2").unwrap_err();
+        assert_eq!(error.line, 3);
+
+        let error =
+            parse_input("seeds: 79 1 4\r\n\r\nseed-to-soil map:\r\n50 98 2 1\r\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+    }
+
+    #[test]
+    fn test_seed_generation() {
+        let input = "79 14 55 13";
+        let mut expected_result = Vec::new();
+
+        for num in 79..93 {
+            expected_result.push(num);
+        }
+
+        for num in 55..68 {
+            expected_result.push(num);
+        }
+
+        assert_eq!(_generate_seeds(input).unwrap(), expected_result);
+    }
+}
@@ -0,0 +1,195 @@
+use aoc_common::{parse_token, read_input, Answer, ParseError, ParseResult, Result, Solver};
+
+#[derive(Debug)]
+struct Race {
+    duration: f64,
+    record_distance: f64,
+}
+
+impl Race {
+    fn new(duration: f64, record_distance: f64) -> Self {
+        Self {
+            duration,
+            record_distance,
+        }
+    }
+}
+
+/// Line `line_number` of the input, which has to start with `label`
+fn labeled_line<'a>(input: &'a str, line_number: usize, label: &str) -> ParseResult<&'a str> {
+    let line = input.lines().nth(line_number - 1).unwrap_or("");
+
+    let values = line.strip_prefix(label).ok_or_else(|| {
+        let message = format!("expected the `{}` line", label);
+        ParseError::new(line, line, &message).on_line(line_number)
+    })?;
+
+    Ok(values)
+}
+
+fn parse_races(input: &str) -> ParseResult<Vec<Race>> {
+    let mut race_numbers: Vec<Vec<f64>> = Vec::new();
+
+    for (line_number, label) in [(1, "Time:"), (2, "Distance:")] {
+        let line = input.lines().nth(line_number - 1).unwrap_or("");
+        let values = labeled_line(input, line_number, label)?;
+
+        let numbers = values
+            .split_whitespace()
+            .map(|value| parse_token::<f64>(line, value))
+            .collect::<ParseResult<Vec<f64>>>()
+            .map_err(|e| e.on_line(line_number))?;
+
+        race_numbers.push(numbers);
+    }
+
+    let race_records = race_numbers.pop().unwrap_or_default();
+    let race_durations = race_numbers.pop().unwrap_or_default();
+
+    if race_durations.len() != race_records.len() {
+        let line = input.lines().nth(1).unwrap_or("");
+        let message = format!("expected {} distances", race_durations.len());
+        return Err(ParseError::at_end(line, &message).on_line(2));
+    }
+
+    let races: Vec<Race> = race_durations
+        .into_iter()
+        .zip(race_records)
+        .map(|(duration, record_distance)| Race::new(duration, record_distance))
+        .collect();
+
+    Ok(races)
+}
+
+fn parse_race_with_kerning(input: &str) -> ParseResult<Race> {
+    // There is only one race, the spaces between the numbers are just bad kerning
+    let mut race_numbers: Vec<f64> = Vec::new();
+
+    for (line_number, label) in [(1, "Time:"), (2, "Distance:")] {
+        let line = input.lines().nth(line_number - 1).unwrap_or("");
+        let values = labeled_line(input, line_number, label)?;
+
+        let mut characters = values.char_indices();
+        if let Some((position, c)) = characters.find(|(_, c)| !c.is_ascii_digit() && *c != ' ') {
+            let token = &values[position..position + c.len_utf8()];
+            let error = ParseError::new(line, token, "expected a digit");
+            return Err(error.on_line(line_number));
+        }
+
+        let digits: String = values.chars().filter(|c| c.is_ascii_digit()).collect();
+        let number = digits
+            .parse::<f64>()
+            .map_err(|_| ParseError::at_end(line, "expected a number").on_line(line_number))?;
+
+        race_numbers.push(number);
+    }
+
+    Ok(Race::new(race_numbers[0], race_numbers[1]))
+}
+
+pub struct Solution {
+    races: Vec<Race>,
+    race_with_kerning: Race,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            races: parse_races(input)?,
+            race_with_kerning: parse_race_with_kerning(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(solve(&self.races).into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(solve(std::slice::from_ref(&self.race_with_kerning)).into())
+    }
+}
+
+fn solve(races: &[Race]) -> u64 {
+    // Basically solve {time=(time-x)*x - record} and get int's between the answers
+    // -x^2 +time*x - record = 0
+    // d = (time*time) - 4 *(record*x)/
+    // Both parts have the same solution
+
+    let mut answer = 1;
+
+    for race in races {
+        let mut tmp = 0;
+        let d = ((race.duration * race.duration) - 4.0 * race.record_distance).sqrt();
+        let mut x_1 = (race.duration - d) / 2.0;
+        let mut x_2 = (race.duration + d) / 2.0;
+
+        // If the boundaries are whole, we skip them since they give the exact record time, not faster
+        if x_1.fract() == 0.0 {
+            x_1 += 1.0;
+        } else {
+            x_1 = x_1.ceil();
+        }
+
+        if x_2.fract() != 0.0 {
+            x_2 = x_2.ceil();
+        }
+
+        for _ in x_1 as u64..x_2 as u64 {
+            tmp += 1;
+        }
+        answer *= tmp;
+    }
+
+    answer
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u64> {
+    let races = parse_races(input)?;
+    Ok(solve(&races))
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u64> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u64> {
+    let race = parse_race_with_kerning(input)?;
+    Ok(solve(&[race]))
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u64> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_solve_part_one() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 288);
+    }
+
+    #[test]
+    fn test_solve_part_two() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 71503);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_races("Time:      7  15   30\nDistance:  9  4O  200").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+
+        let error = parse_races("Time:      7  15   30\nDistance:  9  40").unwrap_err();
+        assert_eq!(error.message, "expected 3 distances");
+
+        let error = parse_races("Time:      7  15   30").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        let error =
+            parse_race_with_kerning("Time:      7  15   30\nDistance:  9  4O  200").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 16));
+    }
+}
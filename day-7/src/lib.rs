@@ -0,0 +1,393 @@
+use std::cmp::{Ordering, PartialOrd};
+use std::collections::HashMap;
+
+use aoc_common::{parse_token, read_input, Answer, ParseError, ParseResult, Result, Solver};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAkind,
+    FullHouse,
+    FourOfAkind,
+    FiveOfAkind,
+}
+#[derive(Debug, PartialEq)]
+struct Hand {
+    real_cards: String,
+    cards: String,
+    bid: u32,
+    kind: HandType,
+}
+
+fn get_hand_type(cards: &str) -> HandType {
+    let mut card_table: HashMap<char, u32> = HashMap::new();
+
+    for card in cards.chars() {
+        *card_table.entry(card).or_insert(0) += 1;
+    }
+
+    let mut count: Vec<&u32> = card_table.values().collect();
+    count.sort();
+
+    match count.as_slice() {
+        [5] => HandType::FiveOfAkind,
+        [1, 4] => HandType::FourOfAkind,
+        [2, 3] => HandType::FullHouse,
+        [1, 1, 3] => HandType::ThreeOfAkind,
+        [1, 2, 2] => HandType::TwoPair,
+        [1, 1, 1, 2] => HandType::OnePair,
+        [1, 1, 1, 1, 1] => HandType::HighCard,
+        _ => unreachable!(),
+    }
+}
+
+/// Cards and bid of a line like "32T3K 765"
+fn parse_hand(line: &str) -> ParseResult<(String, u32)> {
+    let mut parts = line.split_whitespace();
+
+    let cards = match parts.next() {
+        Some(cards) => cards,
+        None => return Err(ParseError::at_end(line, "expected five cards")),
+    };
+
+    let mut characters = cards.char_indices();
+    if let Some((position, card)) = characters.find(|(_, card)| !"23456789TJQKA".contains(*card)) {
+        let token = &cards[position..position + card.len_utf8()];
+        return Err(ParseError::new(line, token, "unknown card"));
+    }
+
+    if cards.len() != 5 {
+        return Err(ParseError::new(line, cards, "expected five cards"));
+    }
+
+    let bid = match parts.next() {
+        Some(bid) => parse_token::<u32>(line, bid)?,
+        None => return Err(ParseError::at_end(line, "expected a bid")),
+    };
+
+    Ok((cards.to_owned(), bid))
+}
+
+impl Hand {
+    fn new(line: &str) -> ParseResult<Self> {
+        let (cards, bid) = parse_hand(line)?;
+
+        let real_cards = cards.clone();
+
+        let mut kind = get_hand_type(&cards);
+
+        let mut cards_candidate = cards.clone();
+        // Iterate over all variants to find the strongest one
+        if cards.contains('J') {
+            let mut possible_replacements: Vec<char> = Vec::new();
+            possible_replacements.push('2');
+            possible_replacements.push('3');
+            possible_replacements.push('4');
+            possible_replacements.push('5');
+            possible_replacements.push('6');
+            possible_replacements.push('7');
+            possible_replacements.push('8');
+            possible_replacements.push('9');
+            possible_replacements.push('T');
+            possible_replacements.push('Q');
+            possible_replacements.push('K');
+            possible_replacements.push('A');
+
+            for candidate in possible_replacements {
+                let new_cards = cards.clone().replace('J', candidate.to_string().as_str());
+                let new_kind = get_hand_type(&new_cards);
+
+                if new_kind >= kind {
+                    kind = new_kind;
+                    cards_candidate = new_cards;
+                }
+            }
+        }
+
+        Ok(Self {
+            real_cards,
+            cards: cards_candidate,
+            bid,
+            kind,
+        })
+    }
+
+    fn without_jokers(line: &str) -> ParseResult<Self> {
+        // Part one rules, J is just a regular card
+        let (cards, bid) = parse_hand(line)?;
+
+        let kind = get_hand_type(&cards);
+
+        Ok(Self {
+            real_cards: cards.clone(),
+            cards,
+            bid,
+            kind,
+        })
+    }
+}
+impl Eq for Hand {}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.kind.cmp(&other.kind) {
+            Ordering::Equal => compare_cards(&self.real_cards, &other.real_cards, 1),
+            other => other,
+        }
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn compare_cards(cards1: &str, cards2: &str, joker_value: u32) -> Ordering {
+    // Jokers are the weakest cards in part two, but in part one J is a Jack between T and Q
+    let mut dict: HashMap<char, u32> = HashMap::new();
+    dict.insert('J', joker_value);
+    dict.insert('2', 2);
+    dict.insert('3', 3);
+    dict.insert('4', 4);
+    dict.insert('5', 5);
+    dict.insert('6', 6);
+    dict.insert('7', 7);
+    dict.insert('8', 8);
+    dict.insert('9', 9);
+    dict.insert('T', 10);
+    dict.insert('Q', 12);
+    dict.insert('K', 13);
+    dict.insert('A', 14);
+
+    for (c1, c2) in cards1.chars().zip(cards2.chars()) {
+        if dict.get(&c1) > dict.get(&c2) {
+            return Ordering::Greater;
+        } else if dict.get(&c1) < dict.get(&c2) {
+            return Ordering::Less;
+        }
+    }
+
+    Ordering::Equal
+}
+
+fn total_winnings(hands: &[Hand]) -> u32 {
+    let mut answer = 0;
+
+    for (i, hand) in hands.iter().enumerate() {
+        let current_bid = (i + 1) as u32 * hand.bid;
+        answer += current_bid;
+    }
+
+    answer
+}
+
+fn winnings_without_jokers(input: &str) -> ParseResult<u32> {
+    let mut hands: Vec<Hand> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let hand = Hand::without_jokers(line).map_err(|e| e.on_line(i + 1))?;
+        hands.push(hand);
+    }
+
+    hands.sort_by(|a, b| match a.kind.cmp(&b.kind) {
+        Ordering::Equal => compare_cards(&a.real_cards, &b.real_cards, 11),
+        other => other,
+    });
+
+    Ok(total_winnings(&hands))
+}
+
+fn winnings_with_jokers(input: &str) -> ParseResult<u32> {
+    let mut hands: Vec<Hand> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        let hand = Hand::new(line).map_err(|e| e.on_line(i + 1))?;
+        hands.push(hand);
+    }
+
+    hands.sort();
+
+    Ok(total_winnings(&hands))
+}
+
+pub struct Solution {
+    // Hands are built differently for each part, so keep the raw input around
+    input: String,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        Ok(Solution {
+            input: input.to_owned(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(winnings_without_jokers(&self.input)?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(winnings_with_jokers(&self.input)?.into())
+    }
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u32> {
+    Ok(winnings_without_jokers(input)?)
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u32> {
+    Ok(winnings_with_jokers(input)?)
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u32> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_solve_part_one() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 6440);
+    }
+
+    #[test]
+    fn test_solve_part_two() {
+        assert_eq!(solve_part_two_str(TEST_INPUT).unwrap(), 5905);
+    }
+
+    #[test]
+    fn test_hand_sort() {
+        let hand_1_input = "JKKK2 12";
+        let hand_2_input = "QQQQ2 12";
+
+        let hand_1 = Hand::new(hand_1_input).unwrap();
+        let hand_2 = Hand::new(hand_2_input).unwrap();
+
+        assert!(hand_1 < hand_2);
+    }
+
+    #[test]
+    fn test_hand_parsing() {
+        let input = "AAAAA 12";
+        let expected_result = Hand {
+            real_cards: String::from("AAAAA"),
+            cards: String::from("AAAAA"),
+            bid: 12,
+            kind: HandType::FiveOfAkind,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        let input = "AA8AA 100";
+        let expected_result = Hand {
+            real_cards: String::from("AA8AA"),
+            cards: String::from("AA8AA"),
+            bid: 100,
+            kind: HandType::FourOfAkind,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        let input = "23332 14";
+        let expected_result = Hand {
+            real_cards: String::from("23332"),
+            cards: String::from("23332"),
+            bid: 14,
+            kind: HandType::FullHouse,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        let input = "TTT98 14";
+        let expected_result = Hand {
+            real_cards: String::from("TTT98"),
+            cards: String::from("TTT98"),
+            bid: 14,
+            kind: HandType::ThreeOfAkind,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        let input = "23432 0";
+        let expected_result = Hand {
+            real_cards: String::from("23432"),
+            cards: String::from("23432"),
+            bid: 0,
+            kind: HandType::TwoPair,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        let input = "A23A4 124";
+        let expected_result = Hand {
+            real_cards: String::from("A23A4"),
+            cards: String::from("A23A4"),
+            bid: 124,
+            kind: HandType::OnePair,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+
+        let input = "23456 121";
+        let expected_result = Hand {
+            real_cards: String::from("23456"),
+            cards: String::from("23456"),
+            bid: 121,
+            kind: HandType::HighCard,
+        };
+
+        assert_eq!(Hand::new(input).unwrap(), expected_result);
+    }
+
+    #[test]
+    fn test_card_genefation_joker() {
+        let input = "T55J5 19";
+        let expeted_result = Hand {
+            real_cards: "T55J5".to_string(),
+            cards: "T5555".to_string(),
+            bid: 19,
+            kind: HandType::FourOfAkind,
+        };
+        assert_eq!(Hand::new(input).unwrap(), expeted_result);
+
+        let input = "TTJ22 19";
+        let expeted_result = Hand {
+            real_cards: "TTJ22".to_string(),
+            cards: "TTT22".to_string(),
+            bid: 19,
+            kind: HandType::FullHouse,
+        };
+        assert_eq!(Hand::new(input).unwrap(), expeted_result);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = Hand::new("32T3X 765").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (5, "X"));
+
+        let error = Hand::without_jokers("32T3 765").unwrap_err();
+        assert_eq!((error.column, error.token.as_str()), (1, "32T3"));
+
+        let error = Hand::new("32T3K").unwrap_err();
+        assert_eq!(
+            (error.column, error.message.as_str()),
+            (6, "expected a bid")
+        );
+
+        let error = winnings_with_jokers("32T3K 765\n\nKK677 28").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+    }
+}
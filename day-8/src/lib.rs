@@ -0,0 +1,266 @@
+use aoc_common::{read_input, Answer, ParseError, ParseResult, Part, Result, Solver};
+
+#[derive(Debug)]
+struct Node {
+    value: String,
+    left: usize,
+    right: usize,
+}
+
+/// Splits "AAA = (BBB, CCC)" into its three names
+fn split_node(line: &str) -> ParseResult<(&str, &str, &str)> {
+    let (value, targets) = line
+        .split_once(" = ")
+        .ok_or_else(|| ParseError::at_end(line, "expected `NAME = (LEFT, RIGHT)`"))?;
+
+    let targets = targets
+        .strip_prefix('(')
+        .and_then(|targets| targets.strip_suffix(')'))
+        .ok_or_else(|| ParseError::new(line, targets, "expected `(LEFT, RIGHT)`"))?;
+
+    let (left, right) = targets
+        .split_once(", ")
+        .ok_or_else(|| ParseError::new(line, targets, "expected `LEFT, RIGHT`"))?;
+
+    Ok((value.trim(), left, right))
+}
+
+impl Node {
+    fn new(line: &str, tmp: &[String]) -> ParseResult<Self> {
+        let (value, left, right) = split_node(line)?;
+
+        let index_left = tmp
+            .iter()
+            .position(|value| value == left)
+            .ok_or_else(|| ParseError::new(line, left, "unknown node"))?;
+        let index_right = tmp
+            .iter()
+            .position(|value| value == right)
+            .ok_or_else(|| ParseError::new(line, right, "unknown node"))?;
+
+        Ok(Self {
+            value: value.to_string(),
+            left: index_left,
+            right: index_right,
+        })
+    }
+}
+
+fn parse_input(input: &str) -> ParseResult<(String, Vec<Node>)> {
+    let mut lines = input.lines();
+    let instructions = lines.next().unwrap_or("").to_owned();
+
+    let mut directions = instructions.char_indices();
+    if let Some((position, c)) = directions.find(|(_, c)| *c != 'L' && *c != 'R') {
+        let token = &instructions[position..position + c.len_utf8()];
+        let error = ParseError::new(&instructions, token, "expected `L` or `R`");
+        return Err(error.on_line(1));
+    }
+    if instructions.is_empty() {
+        return Err(ParseError::at_end("", "expected instructions").on_line(1));
+    }
+
+    if let Some(line) = lines.next() {
+        if !line.is_empty() {
+            return Err(ParseError::new(line, line, "expected an empty line").on_line(2));
+        }
+    }
+
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut tmp: Vec<String> = Vec::new();
+
+    // Nodes start at the third line
+    for (i, line) in lines.clone().enumerate() {
+        let (name, _, _) = split_node(line).map_err(|e| e.on_line(i + 3))?;
+        tmp.push(name.to_string());
+    }
+
+    for (i, line) in lines.enumerate() {
+        let node = Node::new(line, &tmp).map_err(|e| e.on_line(i + 3))?;
+        nodes.push(node);
+    }
+
+    Ok((instructions, nodes))
+}
+
+pub struct Solution {
+    instructions: String,
+    nodes: Vec<Node>,
+}
+
+impl Solver for Solution {
+    fn parse(input: &str) -> Result<Self> {
+        let (instructions, nodes) = parse_input(input)?;
+        Ok(Solution {
+            instructions,
+            nodes,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer> {
+        Ok(count_steps(&self.instructions, &self.nodes)?.into())
+    }
+
+    fn part_two(&self) -> Result<Answer> {
+        Ok(count_ghost_steps(&self.instructions, &self.nodes).into())
+    }
+
+    fn warnings(&self, part: Part) -> Vec<String> {
+        if part == Part::One {
+            return Vec::new();
+        }
+
+        ghost_cycles(&self.instructions, &self.nodes)
+            .into_iter()
+            .filter(|cycle| cycle % CYCLE_PRIME != 0)
+            .map(|cycle| {
+                format!(
+                    "a path cycles in {} steps which is not a multiple of {}, the answer is likely wrong",
+                    cycle, CYCLE_PRIME
+                )
+            })
+            .collect()
+    }
+}
+
+fn count_steps(instructions: &str, nodes: &[Node]) -> Result<u32> {
+    let mut answer = 0;
+
+    // Part two's inputs don't need one
+    let mut current_node = nodes
+        .iter()
+        .find(|&node| node.value == "AAA")
+        .ok_or("no `AAA` node to start from")?;
+
+    let mut current_instruction_index = 0;
+    while current_node.value != "ZZZ" {
+        if current_instruction_index >= instructions.len() {
+            current_instruction_index = 0;
+        }
+
+        // `parse_input` only lets `L` and `R` through
+        let direction = instructions.chars().nth(current_instruction_index).unwrap();
+        current_node = match direction {
+            'L' => &nodes[current_node.left],
+            _ => &nodes[current_node.right],
+        };
+
+        current_instruction_index += 1;
+        answer += 1;
+    }
+
+    Ok(answer)
+}
+
+// Magic numbers :D
+// Actually 277 is the only common denominator of answer per path found
+// The Part two task boils down to finding the minimum number that is divisible by all paths lenghts
+// So before I got the number 277, I printed solve_part_one() for each Node that ends with an A and found 277 from there
+const CYCLE_PRIME: u64 = 277;
+
+/// Number of steps from every starting node to its first node that ends with a Z
+fn ghost_cycles(instructions: &str, nodes: &[Node]) -> Vec<u64> {
+    let starting_nodes = nodes.iter().filter(|&node| node.value.ends_with("A"));
+
+    let mut current_instruction_index = 0;
+
+    let mut cycles = Vec::new();
+    for mut current_node in starting_nodes {
+        let mut local_answer = 0;
+
+        while !current_node.value.ends_with("Z") {
+            if current_instruction_index >= instructions.len() {
+                current_instruction_index = 0;
+            }
+
+            let direction = instructions.chars().nth(current_instruction_index).unwrap();
+            current_node = match direction {
+                'L' => &nodes[current_node.left],
+                _ => &nodes[current_node.right],
+            };
+
+            current_instruction_index += 1;
+            local_answer += 1;
+        }
+
+        cycles.push(local_answer);
+    }
+
+    cycles
+}
+
+fn count_ghost_steps(instructions: &str, nodes: &[Node]) -> u64 {
+    // This answer is specific to the exact problem input
+
+    let mut answer: u64 = 1;
+    for cycle in ghost_cycles(instructions, nodes) {
+        let unique_multiplier = cycle / CYCLE_PRIME;
+        answer *= unique_multiplier;
+    }
+
+    // Don't forget the last prime
+    answer *= CYCLE_PRIME;
+    answer
+}
+
+pub fn solve_part_one_str(input: &str) -> Result<u32> {
+    let (instructions, nodes) = parse_input(input)?;
+    count_steps(&instructions, &nodes)
+}
+
+pub fn solve_part_one(filename: &str) -> Result<u32> {
+    solve_part_one_str(&read_input(filename)?)
+}
+
+pub fn solve_part_two_str(input: &str) -> Result<u64> {
+    let (instructions, nodes) = parse_input(input)?;
+    Ok(count_ghost_steps(&instructions, &nodes))
+}
+
+pub fn solve_part_two(filename: &str) -> Result<u64> {
+    solve_part_two_str(&read_input(filename)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_solve_part_one() {
+        assert_eq!(solve_part_one_str(TEST_INPUT).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let solution = Solution::parse("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)").unwrap();
+
+        assert!(solution.warnings(Part::One).is_empty());
+        assert_eq!(solution.warnings(Part::Two).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = parse_input("LLX\n\nAAA = (AAA, AAA)").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 3));
+
+        let error = parse_input("LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA, ZZZ)").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.token.as_str()),
+            (4, 13, "ZZZ")
+        );
+
+        let error = parse_input("LLR\n\nAAA = (BBB BBB)\nBBB = (AAA, ZZZ)").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 8));
+
+        let error = parse_input("LLR\r\n\r\nAAA = (AAA, AAA)\r\n\r\n").unwrap_err();
+        assert_eq!((error.line, error.column), (4, 1));
+
+        // Part two's example parses but has nowhere for part one to start
+        let (instructions, nodes) =
+            parse_input("LR\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)").unwrap();
+        let error = count_steps(&instructions, &nodes).unwrap_err();
+        assert_eq!(error.to_string(), "no `AAA` node to start from");
+    }
+}